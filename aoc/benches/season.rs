@@ -0,0 +1,26 @@
+use aoc::DAYS;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fs;
+
+fn season_benchmark(c: &mut Criterion) {
+    let inputs: Vec<Vec<u8>> = DAYS
+        .iter()
+        .map(|day| fs::read(day.default_input()).unwrap())
+        .collect();
+
+    // a single run of the season takes seconds, so keep to the fewest
+    // samples criterion accepts
+    let mut group = c.benchmark_group("season");
+    group.sample_size(10);
+    group.bench_function("all days", |b| {
+        b.iter(|| {
+            for (day, input) in DAYS.iter().zip(&inputs) {
+                day.run(input, &[1, 2]).unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, season_benchmark);
+criterion_main!(benches);
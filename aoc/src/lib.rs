@@ -0,0 +1,3 @@
+mod registry;
+
+pub use registry::{find, Day, Report, Solved, DAYS};
@@ -0,0 +1,202 @@
+extern crate aoc;
+use aoc::{find, Day, DAYS};
+use std::any::Any;
+use std::time::Duration;
+use std::{env, fs, panic, process};
+
+const USAGE: &str = "\
+usage: aoc run --day <day> [--part <part>] [--input <path>]
+       aoc run --all [--part <part>]";
+
+struct Job {
+    day: &'static Day,
+    parts: Vec<u8>,
+    input: String,
+}
+
+struct Row {
+    day: u8,
+    part: Option<u8>,
+    answer: String,
+    parse: Option<Duration>,
+    solve: Option<Duration>,
+}
+
+fn number<'a, I>(iter: &mut I, flag: &str) -> Result<u8, String>
+where
+    I: Iterator<Item = &'a String>,
+{
+    iter.next()
+        .ok_or(format!("{} requires a value", flag))?
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} must be a number", flag))
+}
+
+fn parse_args(args: &[String]) -> Result<Vec<Job>, String> {
+    let mut iter = args.iter();
+    match iter.next().map(String::as_str) {
+        Some("run") => (),
+        Some(command) => return Err(format!("unknown command '{}'", command)),
+        None => return Err(String::from("missing command")),
+    }
+
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--day" => day = Some(number(&mut iter, "--day")?),
+            "--part" => part = Some(number(&mut iter, "--part")?),
+            "--input" => input = Some(iter.next().ok_or("--input requires a value")?.clone()),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let parts = match part {
+        Some(part @ 1..=2) => vec![part],
+        Some(part) => return Err(format!("invalid part: {}", part)),
+        None => vec![1, 2],
+    };
+
+    match (all, day) {
+        (true, None) => {
+            if input.is_some() {
+                return Err(String::from("--input cannot be combined with --all"));
+            }
+            Ok(DAYS
+                .iter()
+                .map(|day| Job {
+                    day,
+                    parts: parts.clone(),
+                    input: day.default_input(),
+                })
+                .collect())
+        }
+        (false, Some(number)) => {
+            let day = find(number).ok_or(format!("no solution for day {}", number))?;
+            Ok(vec![Job {
+                day,
+                parts,
+                input: input.unwrap_or_else(|| day.default_input()),
+            }])
+        }
+        (true, Some(_)) => Err(String::from("--day cannot be combined with --all")),
+        (false, None) => Err(String::from("either --day or --all is required")),
+    }
+}
+
+fn run(job: &Job) -> Vec<Row> {
+    let failed = |answer| {
+        vec![Row {
+            day: job.day.number,
+            part: None,
+            answer,
+            parse: None,
+            solve: None,
+        }]
+    };
+
+    let input = match fs::read(&job.input) {
+        Ok(input) => input,
+        Err(err) => return failed(format!("error: {}: {}", job.input, err)),
+    };
+
+    // a day that panics gets an error row, so the other days still run
+    match panic::catch_unwind(|| job.day.run(&input, &job.parts)) {
+        Ok(Ok(report)) => {
+            let parse = report.parse;
+            report
+                .solved
+                .into_iter()
+                .enumerate()
+                .map(|(i, solved)| Row {
+                    day: job.day.number,
+                    part: Some(solved.part),
+                    answer: solved.answer.unwrap_or_else(|| String::from("-")),
+                    parse: if i == 0 { Some(parse) } else { None },
+                    solve: Some(solved.elapsed),
+                })
+                .collect()
+        }
+        Ok(Err(err)) => failed(format!("error: {}: {}", job.input, err)),
+        Err(payload) => failed(format!(
+            "error: {}: panicked: {}",
+            job.input,
+            panic_message(&*payload)
+        )),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    match payload.downcast_ref::<&str>() {
+        Some(message) => message,
+        None => payload
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .unwrap_or("unknown cause"),
+    }
+}
+
+fn duration(value: Option<Duration>) -> String {
+    value.map(|d| format!("{:.1?}", d)).unwrap_or_default()
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let jobs = match parse_args(&args) {
+        Ok(jobs) => jobs,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let rows: Vec<Row> = jobs.iter().flat_map(run).collect();
+    let width = rows
+        .iter()
+        .map(|row| row.answer.len())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}",
+        "day",
+        "part",
+        "answer",
+        "parse",
+        "solve",
+        width = width
+    );
+    for row in &rows {
+        println!(
+            "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}",
+            row.day,
+            row.part.map(|part| part.to_string()).unwrap_or_default(),
+            row.answer,
+            duration(row.parse),
+            duration(row.solve),
+            width = width
+        );
+    }
+
+    let total_parse = rows.iter().filter_map(|row| row.parse).sum();
+    let total_solve = rows.iter().filter_map(|row| row.solve).sum();
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}",
+        "",
+        "",
+        "total",
+        duration(Some(total_parse)),
+        duration(Some(total_solve)),
+        width = width
+    );
+
+    if rows.iter().any(|row| row.part.is_none()) {
+        process::exit(1);
+    }
+}
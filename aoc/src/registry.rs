@@ -0,0 +1,96 @@
+use common::{ParseError, Solution};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub struct Solved {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub parse: Duration,
+    pub solved: Vec<Solved>,
+}
+
+pub struct Day {
+    pub number: u8,
+    run: fn(&[u8], &[u8]) -> Result<Report, ParseError>,
+}
+
+impl Day {
+    const fn new<S: Solution>(number: u8) -> Self {
+        Day {
+            number,
+            run: run::<S>,
+        }
+    }
+
+    pub fn run(&self, input: &[u8], parts: &[u8]) -> Result<Report, ParseError> {
+        (self.run)(input, parts)
+    }
+
+    pub fn default_input(&self) -> String {
+        format!(
+            "{}/../d{:02}/input.txt",
+            env!("CARGO_MANIFEST_DIR"),
+            self.number
+        )
+    }
+}
+
+// parses the input once, then times every requested part against the parsed
+// value
+fn run<S: Solution>(input: &[u8], parts: &[u8]) -> Result<Report, ParseError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+    let mut solved = Vec::with_capacity(parts.len());
+    for part in parts {
+        let start = Instant::now();
+        let answer = match part {
+            1 => S::part1(&parsed).map(|value| value.to_string()),
+            2 => S::part2(&parsed).map(|value| value.to_string()),
+            _ => None,
+        };
+        solved.push(Solved {
+            part: *part,
+            answer,
+            elapsed: start.elapsed(),
+        });
+    }
+    Ok(Report { parse, solved })
+}
+
+pub static DAYS: [Day; 25] = [
+    Day::new::<d01::Day01>(1),
+    Day::new::<d02::Day02>(2),
+    Day::new::<d03::Day03>(3),
+    Day::new::<d04::Day04>(4),
+    Day::new::<d05::Day05>(5),
+    Day::new::<d06::Day06>(6),
+    Day::new::<d07::Day07>(7),
+    Day::new::<d08::Day08>(8),
+    Day::new::<d09::Day09>(9),
+    Day::new::<d10::Day10>(10),
+    Day::new::<d11::Day11>(11),
+    Day::new::<d12::Day12>(12),
+    Day::new::<d13::Day13>(13),
+    Day::new::<d14::Day14>(14),
+    Day::new::<d15::Day15>(15),
+    Day::new::<d16::Day16>(16),
+    Day::new::<d17::Day17>(17),
+    Day::new::<d18::Day18>(18),
+    Day::new::<d19::Day19>(19),
+    Day::new::<d20::Day20>(20),
+    Day::new::<d21::Day21>(21),
+    Day::new::<d22::Day22>(22),
+    Day::new::<d23::Day23>(23),
+    Day::new::<d24::Day24>(24),
+    Day::new::<d25::Day25>(25),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
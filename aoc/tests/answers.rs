@@ -0,0 +1,82 @@
+use aoc::{find, DAYS};
+use std::fs;
+
+struct Expected {
+    day: u8,
+    input: String,
+    part: u8,
+    answer: Option<String>,
+}
+
+fn manifest() -> Vec<Expected> {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt");
+    let manifest = fs::read_to_string(path).expect("answers.txt is readable");
+    manifest
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                [day, input, part, answer] => Expected {
+                    day: day.parse().expect("day is a number"),
+                    input: String::from(input),
+                    part: part.parse().expect("part is a number"),
+                    answer: if answer == "-" {
+                        None
+                    } else {
+                        Some(String::from(answer))
+                    },
+                },
+                _ => panic!("answers.txt:{}: expected 4 fields", i + 1),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn every_day_has_real_answers() {
+    let manifest = manifest();
+    for day in DAYS.iter() {
+        for part in 1..=2 {
+            assert!(
+                manifest
+                    .iter()
+                    .any(|e| e.day == day.number && e.input == "input.txt" && e.part == part),
+                "no answer for day {} part {} on input.txt",
+                day.number,
+                part
+            );
+        }
+    }
+}
+
+#[test]
+fn answers_match_manifest() {
+    let mut failures = Vec::new();
+    for expected in manifest() {
+        let day = find(expected.day).expect("day is registered");
+        let path = format!(
+            "{}/../d{:02}/{}",
+            env!("CARGO_MANIFEST_DIR"),
+            expected.day,
+            expected.input
+        );
+        let input = fs::read(&path).unwrap_or_else(|err| panic!("{}: {}", path, err));
+        let answer = match day.run(&input, &[expected.part]) {
+            Ok(mut report) => report.solved.remove(0).answer,
+            Err(err) => Some(format!("error: {}", err)),
+        };
+        if answer != expected.answer {
+            failures.push(format!(
+                "day {} part {} on {}: expected {}, got {}",
+                expected.day,
+                expected.part,
+                expected.input,
+                expected.answer.as_deref().unwrap_or("-"),
+                answer.as_deref().unwrap_or("-")
+            ));
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,68 @@
+use common::LineReader;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::io::{BufRead, BufReader};
+
+// the fixed-buffer page loop the day crates used before LineReader
+fn page_loop<R: BufRead>(mut reader: R) -> usize {
+    let mut total = 0;
+    let mut page = [0; 4096];
+    let mut line_buf: [u8; 1024] = [0; 1024];
+    let mut line_len = 0;
+    while let Ok(page_len) = reader.read(&mut page) {
+        if page_len == 0 {
+            break;
+        }
+        for c in &page[..page_len] {
+            if c == &b'\n' {
+                total += black_box(&line_buf[..line_len]).len();
+                line_len = 0;
+            } else {
+                line_buf[line_len] = *c;
+                line_len += 1;
+            }
+        }
+    }
+    if line_len > 0 {
+        total += black_box(&line_buf[..line_len]).len();
+    }
+    total
+}
+
+fn line_reader<R: BufRead>(reader: R) -> usize {
+    let mut total = 0;
+    let mut lines = LineReader::new(reader);
+    while let Ok(Some((_, line))) = lines.next_line() {
+        total += black_box(line).len();
+    }
+    total
+}
+
+fn input(lines: usize, width: usize) -> Vec<u8> {
+    let mut input = Vec::with_capacity(lines * (width + 1));
+    for i in 0..lines {
+        input.extend((0..width).map(|j| b'a' + ((i + j) % 26) as u8));
+        input.push(b'\n');
+    }
+    input
+}
+
+fn lines_benchmark(c: &mut Criterion) {
+    for (name, input) in [("short", input(20_000, 4)), ("long", input(1_000, 512))].iter() {
+        c.bench_function(&format!("page loop, slice, {} lines", name), |b| {
+            b.iter(|| page_loop(input.as_slice()))
+        });
+        c.bench_function(&format!("line reader, slice, {} lines", name), |b| {
+            b.iter(|| line_reader(input.as_slice()))
+        });
+        // stdin is read through an 8 KiB buffer
+        c.bench_function(&format!("page loop, buffered, {} lines", name), |b| {
+            b.iter(|| page_loop(BufReader::new(input.as_slice())))
+        });
+        c.bench_function(&format!("line reader, buffered, {} lines", name), |b| {
+            b.iter(|| line_reader(BufReader::new(input.as_slice())))
+        });
+    }
+}
+
+criterion_group!(benches, lines_benchmark);
+criterion_main!(benches);
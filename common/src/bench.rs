@@ -0,0 +1,35 @@
+use crate::{inputs, Solution};
+use criterion::Criterion;
+use std::path::Path;
+
+/// Benchmarks parsing and both parts of a day on every input `inputs` finds
+/// in `dir`, the day's crate directory. Each input gets a group named after
+/// the directory and the file, such as `d01 input.txt`.
+pub fn bench<S: Solution>(c: &mut Criterion, dir: &str) {
+    bench_parts::<S, _>(c, dir, |_, _| true);
+}
+
+/// Same as `bench`, but only benchmarks a part if `keep` returns true for the
+/// input's file name and the part, 1 or 2. Parsing is always benchmarked.
+pub fn bench_parts<S, F>(c: &mut Criterion, dir: &str, keep: F)
+where
+    S: Solution,
+    F: Fn(&str, u8) -> bool,
+{
+    let day = Path::new(dir)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    for (name, input) in inputs(dir).unwrap() {
+        let mut group = c.benchmark_group(format!("{} {}", day, name));
+        group.bench_function("parse", |b| b.iter(|| S::parse(input.as_slice())));
+        let parsed = S::parse(input.as_slice()).unwrap();
+        if keep(&name, 1) {
+            group.bench_function("p1", |b| b.iter(|| S::part1(&parsed)));
+        }
+        if keep(&name, 2) {
+            group.bench_function("p2", |b| b.iter(|| S::part2(&parsed)));
+        }
+        group.finish();
+    }
+}
@@ -0,0 +1,97 @@
+use std::{error, fmt, io};
+
+#[derive(Debug)]
+pub enum ParseError {
+    Io(io::Error),
+    Syntax(SyntaxError),
+}
+
+/// Where the input stopped making sense: 1-based line and column, the bytes
+/// found there (empty at the end of a line or of the input), and a description
+/// of what was expected instead.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub column: usize,
+    pub found: Vec<u8>,
+    pub expected: &'static str,
+}
+
+impl ParseError {
+    pub fn syntax(line: usize, column: usize, found: &[u8], expected: &'static str) -> Self {
+        ParseError::Syntax(SyntaxError {
+            line,
+            column,
+            found: found.to_vec(),
+            expected,
+        })
+    }
+
+    /// Same as `syntax`, with the column worked out from where `found`, a
+    /// slice of `text`, starts in it.
+    pub fn within(line: usize, text: &[u8], found: &[u8], expected: &'static str) -> Self {
+        let offset = (found.as_ptr() as usize)
+            .saturating_sub(text.as_ptr() as usize)
+            .min(text.len());
+        Self::syntax(line, offset + 1, found, expected)
+    }
+
+    /// Same as `syntax`, with the line and column worked out from an offset
+    /// into the complete input. What is found stops at the end of its line.
+    pub fn at_offset(input: &[u8], offset: usize, len: usize, expected: &'static str) -> Self {
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let line = before.iter().filter(|c| **c == b'\n').count() + 1;
+        let column = match before.iter().rposition(|c| *c == b'\n') {
+            Some(newline) => offset - newline,
+            None => offset + 1,
+        };
+        let end = (offset + len).min(input.len());
+        let found = &input[offset..end];
+        let found = found.split(|c| *c == b'\n').next().unwrap_or(found);
+        Self::syntax(line, column, found, expected)
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.found.is_empty() {
+            f.write_str("end of line")
+        } else {
+            write!(
+                f,
+                "\"{}\"",
+                String::from_utf8_lossy(&self.found).escape_debug()
+            )
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(err) => write!(f, "failed to read input: {}", err),
+            ParseError::Syntax(err) => write!(f, "invalid input: {}", err),
+        }
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseError::Io(err) => Some(err),
+            ParseError::Syntax(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(err: io::Error) -> Self {
+        ParseError::Io(err)
+    }
+}
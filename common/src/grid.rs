@@ -0,0 +1,221 @@
+use crate::ParseError;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+const BITS: usize = 64;
+
+/// A rectangle of on/off cells, packed a bit per cell. Every row starts on a
+/// new `u64`, so a row is a plain slice of words.
+///
+/// As text, `#` is a cell that is on and `.` one that is off, with a line per
+/// row.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid with every cell off.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(BITS);
+        BitGrid {
+            width,
+            height,
+            words_per_row,
+            bits: vec![0; words_per_row * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at column `x` of row `y`, or `None` outside the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<bool> {
+        self.row(y)
+            .filter(|row| x < row.len())
+            .map(|row| row.get(x))
+    }
+
+    /// Same as `get`, except that the grid repeats to the left and right, so
+    /// any `x` is inside it.
+    pub fn get_wrapping(&self, x: isize, y: usize) -> Option<bool> {
+        if self.width == 0 {
+            return None;
+        }
+        let x = x.rem_euclid(self.width as isize) as usize;
+        self.row(y).map(|row| row.get(x))
+    }
+
+    /// Turns the cell at column `x` of row `y` on or off.
+    ///
+    /// Panics if the cell is outside the grid.
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        assert!(
+            x < self.width && y < self.height,
+            "cell ({}, {}) is outside a {}x{} grid",
+            x,
+            y,
+            self.width,
+            self.height
+        );
+        let word = &mut self.bits[y * self.words_per_row + x / BITS];
+        let mask = 1 << (x % BITS);
+        if value {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+    }
+
+    pub fn row(&self, y: usize) -> Option<Row<'_>> {
+        if y < self.height {
+            let start = y * self.words_per_row;
+            Some(Row {
+                words: &self.bits[start..start + self.words_per_row],
+                width: self.width,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Row<'_>> {
+        (0..self.height).map(move |y| self.row(y).unwrap())
+    }
+
+    /// The cells of column `x` from top to bottom, or `None` outside the grid.
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = bool> + '_> {
+        if x < self.width {
+            Some(self.rows().map(move |row| row.get(x)))
+        } else {
+            None
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = bool> + '_> {
+        (0..self.width).map(move |x| self.column(x).unwrap())
+    }
+
+    // adds a row below the others; the first row decides the width
+    fn push_row(&mut self, row: &[bool]) {
+        if self.height == 0 {
+            *self = BitGrid::new(row.len(), 0);
+        }
+        let start = self.bits.len();
+        self.bits.resize(start + self.words_per_row, 0);
+        for (x, cell) in row.iter().enumerate() {
+            self.bits[start + x / BITS] |= (*cell as u64) << (x % BITS);
+        }
+        self.height += 1;
+    }
+}
+
+/// One row of a `BitGrid`.
+#[derive(Clone, Copy, Debug)]
+pub struct Row<'a> {
+    words: &'a [u64],
+    width: usize,
+}
+
+impl<'a> Row<'a> {
+    pub fn len(&self) -> usize {
+        self.width
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0
+    }
+
+    /// The cell at column `x`.
+    ///
+    /// Panics if `x` is not less than the width.
+    #[inline]
+    pub fn get(&self, x: usize) -> bool {
+        assert!(x < self.width, "column {} is outside the row", x);
+        self.words[x / BITS] >> (x % BITS) & 1 == 1
+    }
+
+    /// How many cells of the row are on.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + 'a {
+        let row = *self;
+        (0..row.width).map(move |x| row.get(x))
+    }
+}
+
+impl TryFrom<&[u8]> for BitGrid {
+    type Error = ParseError;
+
+    /// Reads the grid from `#` and `.` rows. Every row must be as wide as the
+    /// first, and empty lines are skipped. Lines may end in `\r\n` as well as
+    /// `\n`.
+    fn try_from(buffer: &[u8]) -> Result<BitGrid, ParseError> {
+        let mut grid = BitGrid::default();
+        let mut row = Vec::new();
+        for (i, text) in buffer.split(|c| *c == b'\n').enumerate() {
+            let text = text.strip_suffix(b"\r").unwrap_or(text);
+            if text.is_empty() {
+                continue;
+            }
+            let line = i + 1;
+            let width = if grid.height > 0 {
+                grid.width
+            } else {
+                text.len()
+            };
+
+            row.clear();
+            for (column, c) in text.iter().enumerate() {
+                if column == width {
+                    return Err(ParseError::syntax(line, column + 1, &[*c], "end of row"));
+                }
+                match c {
+                    b'#' => row.push(true),
+                    b'.' => row.push(false),
+                    _ => return Err(ParseError::syntax(line, column + 1, &[*c], "'#' or '.'")),
+                }
+            }
+            if row.len() < width {
+                return Err(ParseError::syntax(line, row.len() + 1, b"", "'#' or '.'"));
+            }
+            grid.push_row(&row);
+        }
+        Ok(grid)
+    }
+}
+
+impl FromStr for BitGrid {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BitGrid::try_from(s.as_bytes())
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut line = String::with_capacity(self.width + 1);
+        for row in self.rows() {
+            line.clear();
+            line.extend(row.iter().map(|cell| if cell { '#' } else { '.' }));
+            line.push('\n');
+            f.write_str(&line)?;
+        }
+        Ok(())
+    }
+}
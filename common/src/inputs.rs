@@ -0,0 +1,28 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Reads a day's `input.txt` and `sample*.txt` from its crate directory,
+/// paired with their file names. The real input comes first and the samples
+/// follow in name order. Files that are not there are left out.
+pub fn inputs<P: AsRef<Path>>(dir: P) -> io::Result<Vec<(String, Vec<u8>)>> {
+    let mut names = Vec::new();
+    for entry in fs::read_dir(dir.as_ref())? {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        if name.starts_with("sample") && name.ends_with(".txt") {
+            names.push(name);
+        }
+    }
+    names.sort();
+    if dir.as_ref().join("input.txt").is_file() {
+        names.insert(0, String::from("input.txt"));
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            let input = fs::read(dir.as_ref().join(&name))?;
+            Ok((name, input))
+        })
+        .collect()
+}
@@ -0,0 +1,26 @@
+#[cfg(feature = "bench")]
+pub mod bench;
+mod error;
+mod grid;
+mod inputs;
+mod lines;
+
+pub use error::{ParseError, SyntaxError};
+pub use grid::{BitGrid, Row};
+pub use inputs::inputs;
+pub use lines::LineReader;
+use std::fmt::Display;
+use std::io::BufRead;
+
+/// The shape every day exposes, so that the runner, benchmarks and tests can
+/// treat the days alike.
+///
+/// `part1` and `part2` return `None` when the input has no solution.
+pub trait Solution {
+    type Input;
+    type Output: Display;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Option<Self::Output>;
+    fn part2(input: &Self::Input) -> Option<Self::Output>;
+}
@@ -0,0 +1,139 @@
+use memchr::memchr;
+use std::io::{self, Read};
+use std::ops::Range;
+
+// most puzzle input lines are this short, and finding their end in one word
+// is quicker than setting up a memchr call for them
+const SHORT_LINE: usize = 8;
+
+/// Reads input one line at a time, however long the line is.
+///
+/// Lines are returned without their `\n` or `\r\n` ending, and the last line
+/// is returned whether or not the input ends with a newline. Input is read a
+/// page at a time into a buffer that only grows when a single line does not
+/// fit in it.
+pub struct LineReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    // the unread part of the buffer
+    start: usize,
+    end: usize,
+    eof: bool,
+    line: usize,
+}
+
+// the position of the first '\n' in the word, found by setting the high bit
+// of every byte that was one; bytes above the first can be set wrongly, which
+// does not matter as only the lowest counts
+#[inline]
+fn short_newline(word: &[u8]) -> Option<usize> {
+    const ONES: u64 = u64::from_le_bytes([0x01; SHORT_LINE]);
+    const HIGHS: u64 = u64::from_le_bytes([0x80; SHORT_LINE]);
+    const NEWLINES: u64 = u64::from_le_bytes([b'\n'; SHORT_LINE]);
+    let mut bytes = [0; SHORT_LINE];
+    bytes.copy_from_slice(word);
+    let word = u64::from_le_bytes(bytes) ^ NEWLINES;
+    let found = word.wrapping_sub(ONES) & !word & HIGHS;
+    if found == 0 {
+        None
+    } else {
+        Some(found.trailing_zeros() as usize / 8)
+    }
+}
+
+impl<R: Read> LineReader<R> {
+    pub fn new(reader: R) -> Self {
+        LineReader {
+            reader,
+            buffer: vec![0; 4096],
+            start: 0,
+            end: 0,
+            eof: false,
+            line: 0,
+        }
+    }
+
+    /// Returns the next line and its 1-based line number, or `None` at the end
+    /// of the input.
+    #[inline]
+    pub fn next_line(&mut self) -> io::Result<Option<(usize, &[u8])>> {
+        let start = self.start;
+        let unread = &self.buffer[start..self.end];
+        let newline = match unread.get(..SHORT_LINE) {
+            Some(word) => match short_newline(word) {
+                Some(len) => Some(len),
+                None => memchr(b'\n', &unread[SHORT_LINE..]).map(|len| SHORT_LINE + len),
+            },
+            None => memchr(b'\n', unread),
+        };
+        match newline {
+            Some(len) => {
+                self.start = start + len + 1;
+                Ok(Some(self.take(start..start + len)))
+            }
+            None => self.read_line(),
+        }
+    }
+
+    /// Reads the rest of the input into one buffer, with every line ending in
+    /// a single `\n`.
+    pub fn read_to_end(mut self) -> io::Result<Vec<u8>> {
+        let mut result = Vec::new();
+        while let Some((_, line)) = self.next_line()? {
+            result.extend_from_slice(line);
+            result.push(b'\n');
+        }
+        Ok(result)
+    }
+
+    #[inline]
+    fn take(&mut self, line: Range<usize>) -> (usize, &[u8]) {
+        self.line += 1;
+        let line = &self.buffer[line];
+        (self.line, line.strip_suffix(b"\r").unwrap_or(line))
+    }
+
+    // the rest of the buffer holds at most part of a line, so read more
+    // input until the line is complete
+    #[cold]
+    fn read_line(&mut self) -> io::Result<Option<(usize, &[u8])>> {
+        loop {
+            let scanned = self.end - self.start;
+            if self.eof {
+                if scanned == 0 {
+                    return Ok(None);
+                }
+                let line = self.start..self.end;
+                self.start = self.end;
+                return Ok(Some(self.take(line)));
+            }
+
+            self.fill()?;
+            if let Some(len) = memchr(b'\n', &self.buffer[self.start + scanned..self.end]) {
+                let line = self.start..self.start + scanned + len;
+                self.start = line.end + 1;
+                return Ok(Some(self.take(line)));
+            }
+        }
+    }
+
+    // moves the partial line to the front of the buffer, growing the buffer
+    // if the line already fills it, and reads another page after it
+    fn fill(&mut self) -> io::Result<()> {
+        self.buffer.copy_within(self.start..self.end, 0);
+        self.end -= self.start;
+        self.start = 0;
+        if self.end == self.buffer.len() {
+            self.buffer.resize(self.buffer.len() * 2, 0);
+        }
+        loop {
+            match self.reader.read(&mut self.buffer[self.end..]) {
+                Ok(0) => self.eof = true,
+                Ok(len) => self.end += len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+            return Ok(());
+        }
+    }
+}
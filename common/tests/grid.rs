@@ -0,0 +1,122 @@
+use common::BitGrid;
+use std::convert::TryFrom;
+
+const GLIDER: &str = ".#.\n..#\n###\n";
+
+#[test]
+fn text_round_trips() {
+    let grid: BitGrid = GLIDER.parse().unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 3));
+    assert_eq!(grid.to_string(), GLIDER);
+    assert_eq!(grid.to_string().parse::<BitGrid>().unwrap(), grid);
+
+    let empty: BitGrid = "".parse().unwrap();
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+    assert_eq!(empty.to_string(), "");
+}
+
+#[test]
+fn skips_empty_lines_and_carriage_returns() {
+    let grid = BitGrid::try_from(&b"\n.#.\r\n..#\r\n\r\n###"[..]).unwrap();
+    assert_eq!(grid.to_string(), GLIDER);
+}
+
+#[test]
+fn rejects_ragged_rows() {
+    let err = ".#.\n..#.\n###\n".parse::<BitGrid>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid input: line 2, column 4: expected end of row, found \".\""
+    );
+    let err = ".#.\n..\n###\n".parse::<BitGrid>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid input: line 2, column 3: expected '#' or '.', found end of line"
+    );
+    let err = ".#.\n.x#\n".parse::<BitGrid>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid input: line 2, column 2: expected '#' or '.', found \"x\""
+    );
+}
+
+#[test]
+fn get_is_none_outside_the_grid() {
+    let grid: BitGrid = GLIDER.parse().unwrap();
+    assert_eq!(grid.get(1, 0), Some(true));
+    assert_eq!(grid.get(0, 0), Some(false));
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 3), None);
+    assert_eq!(BitGrid::new(0, 0).get(0, 0), None);
+}
+
+#[test]
+fn get_wrapping_repeats_to_both_sides() {
+    let grid: BitGrid = GLIDER.parse().unwrap();
+    for x in -7..7isize {
+        let expected = grid.get(x.rem_euclid(3) as usize, 1);
+        assert_eq!(grid.get_wrapping(x, 1), expected, "x = {}", x);
+    }
+    assert_eq!(grid.get_wrapping(-1, 1), Some(true));
+    assert_eq!(grid.get_wrapping(-3, 0), Some(false));
+    assert_eq!(grid.get_wrapping(-1, 3), None);
+    assert_eq!(BitGrid::new(0, 2).get_wrapping(-1, 0), None);
+}
+
+#[test]
+fn reads_columns_top_to_bottom() {
+    let grid: BitGrid = GLIDER.parse().unwrap();
+    assert_eq!(
+        grid.column(2).unwrap().collect::<Vec<_>>(),
+        vec![false, true, true]
+    );
+    assert!(grid.column(3).is_none());
+
+    let columns: Vec<Vec<bool>> = grid.columns().map(|column| column.collect()).collect();
+    assert_eq!(
+        columns,
+        vec![
+            vec![false, false, true],
+            vec![true, false, true],
+            vec![false, true, true],
+        ]
+    );
+}
+
+#[test]
+fn rows_wider_than_a_word() {
+    // 130 cells span three words, with the last one partly used
+    let text: String = (0..3)
+        .map(|y| {
+            let mut line: String = (0..130)
+                .map(|x| if (x + y) % 7 == 0 { '#' } else { '.' })
+                .collect();
+            line.push('\n');
+            line
+        })
+        .collect();
+    let mut grid: BitGrid = text.parse().unwrap();
+    assert_eq!((grid.width(), grid.height()), (130, 3));
+    assert_eq!(grid.to_string(), text);
+
+    for (y, row) in grid.rows().enumerate() {
+        let ones = (0..130).filter(|x| (x + y) % 7 == 0).count();
+        assert_eq!(row.count_ones(), ones);
+    }
+    assert_eq!(grid.get(63, 0), Some(true));
+    assert_eq!(grid.get(64, 1), Some(false));
+    assert_eq!(grid.get(129, 0), Some(false));
+    assert_eq!(grid.get(130, 0), None);
+    assert_eq!(grid.get_wrapping(-1, 2), Some(false));
+    assert_eq!(grid.get_wrapping(-6, 2), Some(true));
+
+    grid.set(64, 1, true);
+    grid.set(126, 0, false);
+    assert_eq!(grid.get(64, 1), Some(true));
+    assert_eq!(grid.get(126, 0), Some(false));
+    assert_eq!(
+        grid.column(64).unwrap().collect::<Vec<_>>(),
+        vec![false, true, false]
+    );
+    assert_eq!(grid.to_string().parse::<BitGrid>().unwrap(), grid);
+}
@@ -0,0 +1,108 @@
+use common::LineReader;
+use std::io::{self, Read};
+
+fn lines<R: Read>(reader: R) -> Vec<(usize, String)> {
+    let mut lines = LineReader::new(reader);
+    let mut result = Vec::new();
+    while let Some((line, text)) = lines.next_line().unwrap() {
+        result.push((line, String::from_utf8(text.to_vec()).unwrap()));
+    }
+    result
+}
+
+fn numbered(texts: &[&str]) -> Vec<(usize, String)> {
+    texts
+        .iter()
+        .enumerate()
+        .map(|(i, text)| (i + 1, String::from(*text)))
+        .collect()
+}
+
+// hands out its input a byte at a time, the way a slow pipe might
+struct OneByte<'a>(&'a [u8]);
+
+impl Read for OneByte<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match (self.0.split_first(), buf.first_mut()) {
+            (Some((byte, rest)), Some(first)) => {
+                *first = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn numbers_lines_from_one_including_blank_ones() {
+    assert_eq!(
+        lines("a\n\nb\n\n\nc\n".as_bytes()),
+        numbered(&["a", "", "b", "", "", "c"])
+    );
+}
+
+#[test]
+fn strips_carriage_returns_before_newlines_only() {
+    assert_eq!(
+        lines("a\r\n\r\nb\rc\r\nd\r".as_bytes()),
+        numbered(&["a", "", "b\rc", "d"])
+    );
+    let text = LineReader::new("a\r\nb\r\n\r\nc".as_bytes())
+        .read_to_end()
+        .unwrap();
+    assert_eq!(text, b"a\nb\n\nc\n");
+}
+
+#[test]
+fn returns_the_last_line_without_a_newline() {
+    assert_eq!(lines("a\nbc".as_bytes()), numbered(&["a", "bc"]));
+    assert_eq!(lines("a\n".as_bytes()), numbered(&["a"]));
+    assert_eq!(lines("\n".as_bytes()), numbered(&[""]));
+}
+
+#[test]
+fn empty_input_has_no_lines() {
+    assert_eq!(lines("".as_bytes()), numbered(&[]));
+    assert_eq!(LineReader::new("".as_bytes()).read_to_end().unwrap(), b"");
+
+    let mut reader = LineReader::new("".as_bytes());
+    assert_eq!(reader.next_line().unwrap(), None);
+    assert_eq!(reader.next_line().unwrap(), None);
+}
+
+// lines around the length of the word that is searched without memchr
+#[test]
+fn lines_either_side_of_a_word() {
+    let texts = ["abcdefg", "abcdefgh", "abcdefghi", "", "abcdefgh"];
+    let input = texts.join("\n");
+    assert_eq!(lines(input.as_bytes()), numbered(&texts));
+    assert_eq!(lines(format!("{}\n", input).as_bytes()), numbered(&texts));
+    assert_eq!(
+        lines("abcdefg\nabcdefgh\nabcdefghi\n".as_bytes()),
+        numbered(&texts[..3])
+    );
+}
+
+#[test]
+fn grows_for_lines_longer_than_the_buffer() {
+    let long: String = (0..10_000)
+        .map(|i| (b'a' + (i % 26) as u8) as char)
+        .collect();
+    let input = format!("short\n{}\r\nafter\n{}", long, long);
+    assert_eq!(
+        lines(input.as_bytes()),
+        numbered(&["short", &long, "after", &long])
+    );
+}
+
+#[test]
+fn lines_split_across_reads() {
+    let long = "x".repeat(5000);
+    let input = format!("a\r\n\nabcdefgh\n{}\nabcdefghi", long);
+    assert_eq!(
+        lines(OneByte(input.as_bytes())),
+        numbered(&["a", "", "abcdefgh", &long, "abcdefghi"])
+    );
+    assert_eq!(lines(OneByte(b"")), numbered(&[]));
+}
@@ -1,78 +1,222 @@
-use std::io::BufRead;
 use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
+use std::cmp::Ordering;
+use std::io::BufRead;
+
+fn parse_item(item: &[u8], line: usize) -> Result<i64, ParseError> {
+    btoi(item).map_err(|_| ParseError::syntax(line, 1, item, "integer"))
+}
 
-pub fn parse<R>(mut reader: R) -> Vec<i32>
+pub fn parse<R>(reader: R) -> Result<Vec<i64>, ParseError>
 where
     R: BufRead,
 {
     let mut result = Vec::new();
-    let mut page = [0; 2048];
-    let mut item: [u8; 16] = [0; 16];
-    let mut item_len = 0;
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        for c in &page[..page_len] {
-            if c == &b'\n' {
-                result.push(btoi(&item[..item_len]).expect("valid integer"));
-                item_len = 0;
-            } else {
-                item[item_len] = *c;
-                item_len += 1;
-            }
+    let mut lines = LineReader::new(reader);
+    while let Some((line, item)) = lines.next_line()? {
+        if !item.is_empty() {
+            result.push(parse_item(item, line)?);
         }
     }
-    if item_len > 0 {
-        result.push(btoi(&item[..item_len]).expect("valid integer"));
+    Ok(result)
+}
+
+/// Entries that add up to the target, in the order they appear in the input:
+/// their 0-based positions and their values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KSum {
+    pub indices: Vec<usize>,
+    pub values: Vec<i64>,
+}
+
+impl KSum {
+    /// The product of the values, or `None` if it does not fit in an `i64`.
+    pub fn product(&self) -> Option<i64> {
+        self.values
+            .iter()
+            .try_fold(1i64, |product, value| product.checked_mul(*value))
     }
+}
+
+/// Finds `k` different entries that add up to `target`. Asking for no entries
+/// finds nothing.
+pub fn find_k_sum(values: &[i64], k: usize, target: i64) -> Option<KSum> {
+    let mut result = None;
+    search_k_sum(values, k, target, |found| {
+        result = Some(found);
+        false
+    });
     result
 }
 
-pub fn p1_solve(a: &[i32], expected_value: usize) -> Option<i32> {
-    let mut map: Vec<bool> = Vec::with_capacity(expected_value);
-    map.resize(expected_value, false);
-    for value in a {
-        map[*value as usize] = true;
+/// Finds every set of `k` different entries that adds up to `target`. Entries
+/// with equal values are still different entries, so each of them makes its
+/// own solution.
+pub fn find_all_k_sums(values: &[i64], k: usize, target: i64) -> Vec<KSum> {
+    let mut result = Vec::new();
+    search_k_sum(values, k, target, |found| {
+        result.push(found);
+        true
+    });
+    result
+}
+
+// values paired with their positions, sorted by value
+type Entries = [(i64, usize)];
+
+// calls `found` with each solution until it returns false
+fn search_k_sum<F>(values: &[i64], k: usize, target: i64, mut found: F)
+where
+    F: FnMut(KSum) -> bool,
+{
+    if k == 0 || k > values.len() {
+        return;
     }
-    let mut diff;
-    for value in a {
-        diff = expected_value as i32 - value;
-        if map[diff as usize] {
-            return Some(value * diff);
+    let mut sorted: Vec<(i64, usize)> = values.iter().copied().zip(0..).collect();
+    sorted.sort_unstable();
+    let mut chosen = Vec::with_capacity(k);
+    search(&sorted, k, target as i128, &mut chosen, &mut |chosen| {
+        let mut entries = chosen.to_vec();
+        entries.sort_unstable_by_key(|(_, index)| *index);
+        found(KSum {
+            indices: entries.iter().map(|(_, index)| *index).collect(),
+            values: entries.iter().map(|(value, _)| *value).collect(),
+        })
+    });
+}
+
+// sums are kept in i128 so that adding up large values can't overflow;
+// returns false once the search should stop
+fn search<F>(
+    sorted: &Entries,
+    k: usize,
+    target: i128,
+    chosen: &mut Vec<(i64, usize)>,
+    found: &mut F,
+) -> bool
+where
+    F: FnMut(&Entries) -> bool,
+{
+    let sum = |entries: &Entries| entries.iter().map(|(v, _)| *v as i128).sum::<i128>();
+    match k {
+        1 => {
+            for entry in sorted.iter().skip_while(|(v, _)| (*v as i128) < target) {
+                if entry.0 as i128 != target {
+                    break;
+                }
+                chosen.push(*entry);
+                let more = found(chosen);
+                chosen.pop();
+                if !more {
+                    return false;
+                }
+            }
+            true
+        }
+        2 => pairs(sorted, target, chosen, found),
+        _ => {
+            for i in 0..=sorted.len().saturating_sub(k) {
+                // the smallest and largest sums that start with this entry
+                if sum(&sorted[i..i + k]) > target {
+                    break;
+                }
+                if sorted[i].0 as i128 + sum(&sorted[sorted.len() - (k - 1)..]) < target {
+                    continue;
+                }
+                chosen.push(sorted[i]);
+                let more = search(
+                    &sorted[i + 1..],
+                    k - 1,
+                    target - sorted[i].0 as i128,
+                    chosen,
+                    found,
+                );
+                chosen.pop();
+                if !more {
+                    return false;
+                }
+            }
+            true
         }
     }
-    None
 }
 
-pub fn p2_solve(a: &[i32], expected_value: usize) -> Option<i32> {
-    let mut map: Vec<bool> = Vec::with_capacity(expected_value);
-    map.resize(expected_value, false);
-    for value in a {
-        map[*value as usize] = true;
+// walks in from both ends; when the ends add up, every entry with the low
+// value pairs with every entry with the high value
+fn pairs<F>(sorted: &Entries, target: i128, chosen: &mut Vec<(i64, usize)>, found: &mut F) -> bool
+where
+    F: FnMut(&Entries) -> bool,
+{
+    if sorted.len() < 2 {
+        return true;
     }
+    let (mut low, mut high) = (0, sorted.len() - 1);
+    while low < high {
+        let (low_value, high_value) = (sorted[low].0, sorted[high].0);
+        match (low_value as i128 + high_value as i128).cmp(&target) {
+            Ordering::Less => low += 1,
+            Ordering::Greater => high -= 1,
+            Ordering::Equal => {
+                let low_end = if low_value == high_value {
+                    high
+                } else {
+                    low + sorted[low..]
+                        .iter()
+                        .take_while(|(v, _)| *v == low_value)
+                        .count()
+                        - 1
+                };
+                let high_start = high + 1
+                    - sorted[..=high]
+                        .iter()
+                        .rev()
+                        .take_while(|(v, _)| *v == high_value)
+                        .count();
+                for i in low..=low_end {
+                    for j in high_start.max(i + 1)..=high {
+                        chosen.push(sorted[i]);
+                        chosen.push(sorted[j]);
+                        let more = found(chosen);
+                        chosen.truncate(chosen.len() - 2);
+                        if !more {
+                            return false;
+                        }
+                    }
+                }
+                if low_value == high_value {
+                    break;
+                }
+                low = low_end + 1;
+                high = high_start - 1;
+            }
+        }
+    }
+    true
+}
 
-    let sorted = map
-        .iter()
-        .enumerate()
-        .filter_map(|(v, s)| match s {
-            true => Some(v as i32),
-            false => None,
-        })
-        .collect::<Vec<_>>();
+pub fn p1_solve(a: &[i64], expected_value: i64) -> Option<i64> {
+    find_k_sum(a, 2, expected_value)?.product()
+}
 
-    let mut diff;
+pub fn p2_solve(a: &[i64], expected_value: i64) -> Option<i64> {
+    find_k_sum(a, 3, expected_value)?.product()
+}
 
-    for a_value in &sorted {
-        for b_value in &sorted {
-            diff = expected_value as i32 - b_value - a_value;
-            if diff < 1 {
-                break;
-            }
-            if map[diff as usize] {
-                return Some(a_value * b_value * diff);
-            }
-        }
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+    type Output = i64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input, 2020)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input, 2020)
     }
-    None
 }
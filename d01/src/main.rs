@@ -1,37 +1,154 @@
 extern crate d01;
-use d01::{p1_solve, p2_solve, parse};
-use std::env;
+use d01::{find_all_k_sums, find_k_sum, parse, KSum};
 use std::io::stdin;
+use std::{env, process};
+
+const USAGE: &str = "\
+usage: d01 [--k <k>] [--target <target>] [--all] [--format text|json] < input.txt";
+
+// exit codes besides success
+const INVALID_INPUT: i32 = 1;
+const INVALID_USAGE: i32 = 2;
+const NO_SOLUTION: i32 = 3;
+
+enum Format {
+    Text,
+    Json,
+}
+
+struct Options {
+    k: usize,
+    target: i64,
+    all: bool,
+    format: Format,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        k: 2,
+        target: 2020,
+        all: false,
+        format: Format::Text,
+    };
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || iter.next().ok_or(format!("{} requires a value", arg));
+        match arg.as_str() {
+            "--k" => {
+                options.k = value()?
+                    .trim()
+                    .parse()
+                    .map_err(|_| String::from("--k must be a positive number"))?
+            }
+            "--target" => {
+                options.target = value()?
+                    .trim()
+                    .parse()
+                    .map_err(|_| String::from("--target must be a number"))?
+            }
+            "--all" => options.all = true,
+            "--format" => {
+                options.format = match value()?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    format => return Err(format!("unknown format '{}'", format)),
+                }
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    if options.k == 0 {
+        return Err(String::from("--k must be a positive number"));
+    }
+    Ok(options)
+}
+
+// entries are numbered from 1 in the output, like lines
+fn text(solution: &KSum, target: i64) -> String {
+    let sum = solution
+        .values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let entries = solution
+        .indices
+        .iter()
+        .map(|index| (index + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let product = solution
+        .product()
+        .map(|product| product.to_string())
+        .unwrap_or_else(|| String::from("out of range"));
+    format!(
+        "{} = {} (entries {}), product {}",
+        sum, target, entries, product
+    )
+}
+
+fn json(solution: &KSum) -> String {
+    let entries = solution
+        .indices
+        .iter()
+        .zip(&solution.values)
+        .map(|(index, value)| format!("{{\"entry\":{},\"value\":{}}}", index + 1, value))
+        .collect::<Vec<_>>()
+        .join(",");
+    let product = solution
+        .product()
+        .map(|product| product.to_string())
+        .unwrap_or_else(|| String::from("null"));
+    format!("{{\"entries\":[{}],\"product\":{}}}", entries, product)
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    let part = args
-        .get(1)
-        .expect("required [part]")
-        .trim()
-        .parse()
-        .expect("invalid [part]");
-
-    let expected_value = args
-        .get(2)
-        .expect("required [value]")
-        .trim()
-        .parse()
-        .expect("invalid [value]");
-
-    let parsed = parse(stdin().lock());
-
-    let result = match part {
-        1 => p1_solve(&parsed, expected_value),
-        2 => p2_solve(&parsed, expected_value),
-        _ => {
-            println!("Invalid part: {}", part);
-            None
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(INVALID_USAGE);
         }
     };
 
-    if let Some(value) = result {
-        println!("{}", value);
+    let parsed = match parse(stdin().lock()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(INVALID_INPUT);
+        }
+    };
+
+    let solutions = if options.all {
+        find_all_k_sums(&parsed, options.k, options.target)
+    } else {
+        find_k_sum(&parsed, options.k, options.target)
+            .into_iter()
+            .collect()
+    };
+
+    match options.format {
+        Format::Text => {
+            for solution in &solutions {
+                println!("{}", text(solution, options.target));
+            }
+            if solutions.is_empty() {
+                eprintln!("no {} entries add up to {}", options.k, options.target);
+            }
+        }
+        Format::Json => println!(
+            "{{\"k\":{},\"target\":{},\"solutions\":[{}]}}",
+            options.k,
+            options.target,
+            solutions.iter().map(json).collect::<Vec<_>>().join(",")
+        ),
+    }
+
+    if solutions.is_empty() {
+        process::exit(NO_SOLUTION);
     }
 }
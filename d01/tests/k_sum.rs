@@ -0,0 +1,130 @@
+use d01::{find_all_k_sums, find_k_sum, KSum};
+
+// every set of k positions, in increasing order, whose values add up to the
+// target, checked in i128 like the search does
+fn brute_force(values: &[i64], k: usize, target: i64) -> Vec<KSum> {
+    fn walk(
+        values: &[i64],
+        k: usize,
+        target: i64,
+        start: usize,
+        chosen: &mut Vec<usize>,
+        result: &mut Vec<KSum>,
+    ) {
+        if chosen.len() == k {
+            let sum: i128 = chosen.iter().map(|i| values[*i] as i128).sum();
+            if sum == target as i128 {
+                result.push(KSum {
+                    indices: chosen.clone(),
+                    values: chosen.iter().map(|i| values[*i]).collect(),
+                });
+            }
+            return;
+        }
+        for i in start..values.len() {
+            chosen.push(i);
+            walk(values, k, target, i + 1, chosen, result);
+            chosen.pop();
+        }
+    }
+
+    let mut result = Vec::new();
+    if k > 0 {
+        walk(values, k, target, 0, &mut Vec::new(), &mut result);
+    }
+    result
+}
+
+fn check(values: &[i64], k: usize, target: i64) {
+    let expected = brute_force(values, k, target);
+    let mut found = find_all_k_sums(values, k, target);
+    found.sort_by(|a, b| a.indices.cmp(&b.indices));
+    assert_eq!(
+        found, expected,
+        "{:?}, k = {}, target = {}",
+        values, k, target
+    );
+
+    match find_k_sum(values, k, target) {
+        Some(one) => assert!(expected.contains(&one), "{:?} is not a solution", one),
+        None => assert!(expected.is_empty(), "missed {:?}", expected),
+    }
+}
+
+// a small linear congruential generator, so that the inputs are the same on
+// every run
+struct Numbers(u64);
+
+impl Numbers {
+    fn next(&mut self, below: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) % below
+    }
+}
+
+#[test]
+fn matches_brute_force_with_duplicate_values() {
+    let mut numbers = Numbers(2020);
+    for _ in 0..300 {
+        let len = numbers.next(10) as usize;
+        // a narrow range of values, so that most inputs repeat some
+        let values: Vec<i64> = (0..len).map(|_| numbers.next(9) as i64 - 4).collect();
+        for k in 0..=4 {
+            for target in -8..=8 {
+                check(&values, k, target);
+            }
+        }
+    }
+}
+
+#[test]
+fn matches_brute_force_when_every_value_is_the_same() {
+    for len in 0..7 {
+        let values = vec![3; len];
+        for k in 0..=5 {
+            check(&values, k, 3 * k as i64);
+            check(&values, k, 3 * k as i64 + 1);
+        }
+    }
+}
+
+#[test]
+fn matches_brute_force_at_the_i64_extremes() {
+    let values = [
+        i64::MAX,
+        i64::MIN,
+        i64::MAX,
+        -1,
+        0,
+        1,
+        i64::MIN,
+        i64::MAX - 1,
+        i64::MIN + 1,
+    ];
+    let targets = [
+        i64::MAX,
+        i64::MIN,
+        i64::MAX - 1,
+        i64::MIN + 1,
+        -2,
+        -1,
+        0,
+        1,
+        2,
+    ];
+    for k in 0..=4 {
+        for target in targets.iter() {
+            check(&values, k, *target);
+        }
+    }
+}
+
+#[test]
+fn products_that_overflow_are_none() {
+    let found = find_k_sum(&[i64::MAX, i64::MIN, 2, -1], 2, -1).unwrap();
+    assert_eq!(found.values, vec![i64::MAX, i64::MIN]);
+    assert_eq!(found.product(), None);
+}
@@ -0,0 +1,22 @@
+use d01::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn rejects_lines_that_are_not_integers() {
+    assert_eq!(
+        error("1721\n979\n\n36x\n"),
+        "invalid input: line 4, column 1: expected integer, found \"36x\""
+    );
+    assert_eq!(
+        error("1721 \n"),
+        "invalid input: line 1, column 1: expected integer, found \"1721 \""
+    );
+    assert_eq!(
+        error("99999999999999999999\n"),
+        "invalid input: line 1, column 1: expected integer, found \"99999999999999999999\""
+    );
+    assert_eq!(parse("-5\n\n+7\r\n".as_bytes()).unwrap(), vec![-5, 7]);
+}
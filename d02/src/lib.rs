@@ -1,157 +1,339 @@
 extern crate btoi;
 
 use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
+use std::collections::HashSet;
+use std::fmt;
 use std::io::BufRead;
+use std::str;
+
+/// The rule written in front of a password: two numbers and a letter. What
+/// they mean is up to the policy the password is checked against.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rule {
+    pub first: usize,
+    pub second: usize,
+    pub letter: char,
+}
 
-fn p1_validate(line: &[u8]) -> bool {
-    let mut iter = &mut line.iter();
-
-    let mut buffer = [0; 32];
-    let mut buffer_len = 0;
+/// A password and the rule in front of it. Passwords are UTF-8, and policies
+/// count their characters as Unicode scalar values; passwords that are all
+/// ASCII are checked byte by byte.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PasswordEntry {
+    pub line: usize,
+    pub policy: Rule,
+    pub password: String,
+}
 
-    for c in &mut iter {
-        if c == &b'-' {
-            break;
-        }
-        buffer[buffer_len] = *c;
-        buffer_len += 1;
+impl PasswordEntry {
+    // whether the password and letter can be looked at as bytes
+    fn is_ascii(&self) -> bool {
+        self.policy.letter.is_ascii() && self.password.is_ascii()
     }
+}
 
-    let min = btoi(&buffer[..buffer_len]).expect("minimum number of occurences");
+/// Why a password was not valid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Failure {
+    TooFew,
+    TooMany,
+    BothPositions,
+    NeitherPosition,
+    OutsideClass,
+    TooFewDistinct,
+}
 
-    buffer_len = 0;
-    for c in &mut iter {
-        if c == &b' ' {
-            break;
-        }
-        buffer[buffer_len] = *c;
-        buffer_len += 1;
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Failure::TooFew => "too few occurrences",
+            Failure::TooMany => "too many occurrences",
+            Failure::BothPositions => "both positions matched",
+            Failure::NeitherPosition => "neither position matched",
+            Failure::OutsideClass => "character outside the class",
+            Failure::TooFewDistinct => "too few distinct characters",
+        })
     }
+}
 
-    let max = btoi(&buffer[..buffer_len]).expect("maximum number of occurences");
-
-    let expected_byte = iter.next().expect("expected character");
-
-    iter.next(); // ':'
-    iter.next(); // ' '
+/// A way of deciding whether a password is valid.
+pub trait Policy {
+    fn check(&self, entry: &PasswordEntry) -> Result<(), Failure>;
 
-    let mut occurences = 0;
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        self.check(entry).is_ok()
+    }
+}
 
-    for c in &mut iter {
-        if c == expected_byte {
-            occurences += 1;
+/// The letter occurs at least `first` and at most `second` times.
+pub struct CountRange;
+
+impl Policy for CountRange {
+    fn check(&self, entry: &PasswordEntry) -> Result<(), Failure> {
+        let Rule {
+            first: min,
+            second: max,
+            letter,
+        } = entry.policy;
+        let occurences = if entry.is_ascii() {
+            let letter = letter as u8;
+            entry.password.bytes().filter(|c| *c == letter).count()
+        } else {
+            entry.password.chars().filter(|c| *c == letter).count()
+        };
+        if occurences < min {
+            Err(Failure::TooFew)
+        } else if occurences > max {
+            Err(Failure::TooMany)
+        } else {
+            Ok(())
         }
     }
+}
 
-    occurences >= min && occurences <= max
+/// The letter is at exactly one of the 1-based positions `first` and
+/// `second`, or at that position if they are the same.
+pub struct PositionalXor;
+
+impl Policy for PositionalXor {
+    fn check(&self, entry: &PasswordEntry) -> Result<(), Failure> {
+        let Rule {
+            first,
+            second,
+            letter,
+        } = entry.policy;
+        let ascii = entry.is_ascii();
+        let at = |pos: usize| {
+            pos > 0
+                && if ascii {
+                    entry.password.as_bytes().get(pos - 1) == Some(&(letter as u8))
+                } else {
+                    entry.password.chars().nth(pos - 1) == Some(letter)
+                }
+        };
+        match (at(first), second != first && at(second)) {
+            (true, true) => Err(Failure::BothPositions),
+            (false, false) => Err(Failure::NeitherPosition),
+            _ => Ok(()),
+        }
+    }
 }
 
-pub fn p1_parse_and_solve<R>(mut reader: R) -> usize
-where
-    R: BufRead,
-{
-    let mut page = [0; 4096];
-    let mut buffer = [0; 32];
-    let mut buffer_len = 0;
+/// Every character of the password belongs to a class, written the way
+/// regular expressions write them: `[a-z0-9]`, or `[^ ]` for every character
+/// but the ones listed. A backslash takes the next character literally, and a
+/// `-` at either end of the class is a character of its own.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CharacterClass {
+    negated: bool,
+    ranges: Vec<(char, char)>,
+}
 
-    let mut valid_count = 0;
+impl CharacterClass {
+    pub fn parse(pattern: &str) -> Result<Self, ParseError> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let error = |pos: usize, expected| {
+            let found = pattern.get(pos).map(|c| c.to_string()).unwrap_or_default();
+            ParseError::syntax(1, pos + 1, found.as_bytes(), expected)
+        };
 
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
+        if pattern.first() != Some(&'[') {
+            return Err(error(0, "'['"));
+        }
+        let mut pos = 1;
+        let negated = pattern.get(pos) == Some(&'^');
+        if negated {
+            pos += 1;
         }
 
-        for c in &page[..page_len] {
-            if c == &b'\n' {
-                valid_count += p1_validate(&buffer[..buffer_len]) as usize;
-                buffer_len = 0;
-            } else {
-                buffer[buffer_len] = *c;
-                buffer_len += 1;
-            }
+        // reads one character, unescaping it if needed
+        let character = |pos: usize| match pattern.get(pos) {
+            Some('\\') => match pattern.get(pos + 1) {
+                Some(c) => Ok((*c, pos + 2)),
+                None => Err(error(pos + 1, "escaped character")),
+            },
+            Some(']') | None => Err(error(pos, "character")),
+            Some(c) => Ok((*c, pos + 1)),
+        };
+
+        let mut ranges = Vec::new();
+        while pattern.get(pos) != Some(&']') {
+            let (start, next) = character(pos)?;
+            pos = next;
+            let end = match pattern.get(pos..pos + 2) {
+                Some(['-', c]) if *c != ']' => {
+                    let (end, next) = character(pos + 1)?;
+                    if end < start {
+                        return Err(error(pos + 1, "range end at or after its start"));
+                    }
+                    pos = next;
+                    end
+                }
+                _ => start,
+            };
+            ranges.push((start, end));
+        }
+        if pos + 1 != pattern.len() {
+            return Err(error(pos + 1, "end of pattern"));
         }
-    }
 
-    if buffer_len > 0 {
-        valid_count += p1_validate(&buffer[..buffer_len]) as usize;
+        Ok(CharacterClass { negated, ranges })
     }
 
-    valid_count
+    pub fn contains(&self, c: char) -> bool {
+        let listed = self
+            .ranges
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&c));
+        listed != self.negated
+    }
 }
 
-fn p2_validate(line: &[u8]) -> bool {
-    let mut iter = &mut line.iter();
+impl Policy for CharacterClass {
+    fn check(&self, entry: &PasswordEntry) -> Result<(), Failure> {
+        if entry.password.chars().all(|c| self.contains(c)) {
+            Ok(())
+        } else {
+            Err(Failure::OutsideClass)
+        }
+    }
+}
 
-    let mut buffer = [0; 32];
-    let mut buffer_len = 0;
+/// The password has at least this many different characters.
+pub struct MinDistinct(pub usize);
 
-    for c in &mut iter {
-        if c == &b'-' {
-            break;
+impl Policy for MinDistinct {
+    fn check(&self, entry: &PasswordEntry) -> Result<(), Failure> {
+        let distinct = if entry.is_ascii() {
+            let mut seen = [false; 128];
+            for c in entry.password.bytes() {
+                seen[c as usize] = true;
+            }
+            seen.iter().filter(|seen| **seen).count()
+        } else {
+            entry.password.chars().collect::<HashSet<_>>().len()
+        };
+        if distinct >= self.0 {
+            Ok(())
+        } else {
+            Err(Failure::TooFewDistinct)
         }
-        buffer[buffer_len] = *c;
-        buffer_len += 1;
     }
+}
 
-    let pos_first = btoi::<usize>(&buffer[..buffer_len]).expect("minimum number of occurences");
-
-    buffer_len = 0;
-    for c in &mut iter {
-        if c == &b' ' {
-            break;
+/// Checks every entry against each of the policies in a single pass, and
+/// returns how many entries each policy accepted.
+pub fn count_valid(entries: &[PasswordEntry], policies: &[&dyn Policy]) -> Vec<usize> {
+    let mut result = vec![0; policies.len()];
+    for entry in entries {
+        for (count, policy) in result.iter_mut().zip(policies) {
+            *count += policy.is_valid(entry) as usize;
         }
-        buffer[buffer_len] = *c;
-        buffer_len += 1;
     }
+    result
+}
+
+/// The outcome of checking one entry against a policy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineReport<'a> {
+    pub entry: &'a PasswordEntry,
+    pub result: Result<(), Failure>,
+}
 
-    let pos_second = btoi::<usize>(&buffer[..buffer_len]).expect("maximum number of occurences");
+/// Checks the entries against a policy one at a time, in input order.
+pub fn report<'a>(
+    entries: &'a [PasswordEntry],
+    policy: &'a dyn Policy,
+) -> impl Iterator<Item = LineReport<'a>> {
+    entries.iter().map(move |entry| LineReport {
+        entry,
+        result: policy.check(entry),
+    })
+}
 
-    let expected_byte = iter.next().expect("expected character");
+fn parse_number(
+    line: &[u8],
+    line_no: usize,
+    start: usize,
+    delimiter: u8,
+    expected: &'static str,
+) -> Result<(usize, usize), ParseError> {
+    let len = line[start..]
+        .iter()
+        .position(|c| *c == delimiter)
+        .ok_or_else(|| ParseError::syntax(line_no, line.len() + 1, b"", expected))?;
+    let number = &line[start..start + len];
+    btoi(number)
+        .map(|value| (value, start + len + 1))
+        .map_err(|_| ParseError::syntax(line_no, start + 1, number, "number"))
+}
 
-    iter.next(); // ':'
-    iter.next(); // ' '
+fn parse_line(line: &[u8], line_no: usize) -> Result<PasswordEntry, ParseError> {
+    let text = str::from_utf8(line).map_err(|err| {
+        let start = err.valid_up_to();
+        let end = start + err.error_len().unwrap_or(line.len() - start);
+        ParseError::syntax(line_no, start + 1, &line[start..end], "UTF-8 text")
+    })?;
+    let (first, pos) = parse_number(line, line_no, 0, b'-', "'-'")?;
+    let (second, pos) = parse_number(line, line_no, pos, b' ', "' '")?;
+    let letter = text[pos..]
+        .chars()
+        .next()
+        .ok_or_else(|| ParseError::syntax(line_no, pos + 1, b"", "policy character"))?;
+    let pos = pos + letter.len_utf8() - 1;
+    let separator = &line[pos + 1..(pos + 3).min(line.len())];
+    if separator != b": " {
+        return Err(ParseError::syntax(line_no, pos + 2, separator, "\": \""));
+    }
+    Ok(PasswordEntry {
+        line: line_no,
+        policy: Rule {
+            first,
+            second,
+            letter,
+        },
+        password: String::from(&text[pos + 3..]),
+    })
+}
 
-    let mut valid = false;
-    let mut pos = 1;
-    for c in iter {
-        if (pos == pos_first) || (pos == pos_second) {
-            valid ^= c == expected_byte;
+pub fn parse<R>(reader: R) -> Result<Vec<PasswordEntry>, ParseError>
+where
+    R: BufRead,
+{
+    let mut result = Vec::new();
+    let mut lines = LineReader::new(reader);
+    while let Some((line_no, line)) = lines.next_line()? {
+        if !line.is_empty() {
+            result.push(parse_line(line, line_no)?);
         }
-        pos += 1;
     }
+    Ok(result)
+}
 
-    valid
+pub fn p1_solve(entries: &[PasswordEntry]) -> Option<usize> {
+    Some(count_valid(entries, &[&CountRange])[0])
 }
 
-pub fn p2_parse_and_solve<R>(mut reader: R) -> usize
-    where
-        R: BufRead,
-{
-    let mut page = [0; 4096];
-    let mut buffer = [0; 32];
-    let mut buffer_len = 0;
+pub fn p2_solve(entries: &[PasswordEntry]) -> Option<usize> {
+    Some(count_valid(entries, &[&PositionalXor])[0])
+}
 
-    let mut valid_count = 0;
+pub struct Day02;
 
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
+impl Solution for Day02 {
+    type Input = Vec<PasswordEntry>;
+    type Output = usize;
 
-        for c in &page[..page_len] {
-            if c == &b'\n' {
-                valid_count += p2_validate(&buffer[..buffer_len]) as usize;
-                buffer_len = 0;
-            } else {
-                buffer[buffer_len] = *c;
-                buffer_len += 1;
-            }
-        }
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
     }
 
-    if buffer_len > 0 {
-        valid_count += p2_validate(&buffer[..buffer_len]) as usize;
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
     }
 
-    valid_count
-}
\ No newline at end of file
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
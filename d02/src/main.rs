@@ -1,24 +1,102 @@
 extern crate d02;
-use d02::{p1_parse_and_solve, p2_parse_and_solve};
-use std::{env, io};
+use d02::{report, CountRange, LineReport, Policy, PositionalXor};
+use std::{env, io, process};
+
+const USAGE: &str = "usage: d02 <part> [--report csv|json] < input.txt";
+
+enum Output {
+    Count,
+    Csv,
+    Json,
+}
+
+fn parse_args(args: &[String]) -> Result<(&'static dyn Policy, Output), String> {
+    let mut iter = args.iter();
+    let policy: &dyn Policy = match iter.next().map(|part| part.trim()) {
+        Some("1") => &CountRange,
+        Some("2") => &PositionalXor,
+        Some(part) => return Err(format!("invalid part: {}", part)),
+        None => return Err(String::from("missing part")),
+    };
+
+    let mut output = Output::Count;
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--report" => {
+                output = match iter.next().map(String::as_str) {
+                    Some("csv") => Output::Csv,
+                    Some("json") => Output::Json,
+                    Some(format) => return Err(format!("unknown report format '{}'", format)),
+                    None => return Err(String::from("--report requires a value")),
+                }
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok((policy, output))
+}
+
+fn failure(line: &LineReport) -> String {
+    line.result
+        .err()
+        .map(|failure| failure.to_string())
+        .unwrap_or_default()
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    let part = args
-        .get(1)
-        .expect("Missing argument 'part'")
-        .trim()
-        .parse()
-        .expect("Part must be a number");
-
-    let result = match part {
-        1 => Some(p1_parse_and_solve(io::stdin().lock())),
-        2 => Some(p2_parse_and_solve(io::stdin().lock())),
-        _ => None,
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let (policy, output) = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let parsed = match d02::parse(io::stdin().lock()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
     };
 
-    if let Some(value) = result {
-        println!("{}", value);
+    match output {
+        Output::Count => println!(
+            "{}",
+            report(&parsed, policy)
+                .filter(|line| line.result.is_ok())
+                .count()
+        ),
+        Output::Csv => {
+            println!("line,valid,failure");
+            for line in report(&parsed, policy) {
+                println!(
+                    "{},{},{}",
+                    line.entry.line,
+                    line.result.is_ok(),
+                    failure(&line)
+                );
+            }
+        }
+        Output::Json => {
+            let lines: Vec<String> = report(&parsed, policy)
+                .map(|line| {
+                    let failure = match line.result {
+                        Ok(()) => String::from("null"),
+                        Err(failure) => format!("\"{}\"", failure),
+                    };
+                    format!(
+                        "{{\"line\":{},\"valid\":{},\"failure\":{}}}",
+                        line.entry.line,
+                        line.result.is_ok(),
+                        failure
+                    )
+                })
+                .collect();
+            println!("[{}]", lines.join(","));
+        }
     }
 }
@@ -0,0 +1,37 @@
+use d02::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn points_at_each_part_of_an_entry() {
+    assert_eq!(
+        error("1-3 a: abcde\n\n1 3 b: cdefg\n"),
+        "invalid input: line 3, column 13: expected '-', found end of line"
+    );
+    assert_eq!(
+        error("1-3b:cdefg\n"),
+        "invalid input: line 1, column 11: expected ' ', found end of line"
+    );
+    assert_eq!(
+        error("x-3 b: cdefg\n"),
+        "invalid input: line 1, column 1: expected number, found \"x\""
+    );
+    assert_eq!(
+        error("1--3 b: cdefg\n"),
+        "invalid input: line 1, column 3: expected number, found \"-3\""
+    );
+    assert_eq!(
+        error("1-3 \n"),
+        "invalid input: line 1, column 5: expected policy character, found end of line"
+    );
+    assert_eq!(
+        error("1-3 b cdefg\n"),
+        "invalid input: line 1, column 6: expected \": \", found \" c\""
+    );
+    assert_eq!(
+        error("1-3 ж:cdefg\n"),
+        "invalid input: line 1, column 7: expected \": \", found \":c\""
+    );
+}
@@ -0,0 +1,37 @@
+use d02::{parse, report, CountRange, Failure, Policy, PositionalXor};
+
+// the sample entries, with blank lines around and between them
+const INPUT: &str = "\n1-3 a: abcde\n\n\n1-3 b: cdefg\r\n2-9 c: ccccccccc\n\n";
+
+fn lines(policy: &dyn Policy) -> Vec<(usize, Result<(), Failure>)> {
+    let entries = parse(INPUT.as_bytes()).unwrap();
+    report(&entries, policy)
+        .map(|line| (line.entry.line, line.result))
+        .collect()
+}
+
+#[test]
+fn reports_every_entry_with_its_input_line() {
+    assert_eq!(
+        lines(&CountRange),
+        vec![(2, Ok(())), (5, Err(Failure::TooFew)), (6, Ok(()))]
+    );
+    assert_eq!(
+        lines(&PositionalXor),
+        vec![
+            (2, Ok(())),
+            (5, Err(Failure::NeitherPosition)),
+            (6, Err(Failure::BothPositions)),
+        ]
+    );
+}
+
+#[test]
+fn reports_the_entries_themselves() {
+    let entries = parse(INPUT.as_bytes()).unwrap();
+    let passwords: Vec<&str> = report(&entries, &CountRange)
+        .map(|line| line.entry.password.as_str())
+        .collect();
+    assert_eq!(passwords, vec!["abcde", "cdefg", "ccccccccc"]);
+    assert_eq!(report(&[], &CountRange).count(), 0);
+}
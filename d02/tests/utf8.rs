@@ -0,0 +1,85 @@
+use common::ParseError;
+use d02::{parse, CharacterClass, CountRange, Failure, MinDistinct, Policy, PositionalXor};
+
+fn check(line: &str, policy: &dyn Policy) -> Result<(), Failure> {
+    let entries = parse(line.as_bytes()).unwrap();
+    policy.check(&entries[0])
+}
+
+#[test]
+fn policy_letter_can_be_any_character() {
+    let entries = parse("1-3 ж: жaж\n2-2 😀: a😀b😀\n".as_bytes()).unwrap();
+    assert_eq!(entries[0].policy.letter, 'ж');
+    assert_eq!(entries[0].password, "жaж");
+    assert_eq!(entries[1].policy.letter, '😀');
+    assert_eq!(entries[1].password, "a😀b😀");
+}
+
+#[test]
+fn count_range_counts_characters() {
+    assert_eq!(check("1-2 ü: Grüße-日本-ü", &CountRange), Ok(()));
+    assert_eq!(check("3-5 ж: жaж", &CountRange), Err(Failure::TooFew));
+    assert_eq!(check("1-1 本: 日本本", &CountRange), Err(Failure::TooMany));
+}
+
+#[test]
+fn positions_count_characters_not_bytes() {
+    assert_eq!(
+        check("2-4 a: 日a本a", &PositionalXor),
+        Err(Failure::BothPositions)
+    );
+    assert_eq!(check("1-3 é: éab", &PositionalXor), Ok(()));
+    assert_eq!(
+        check("2-3 λ: αβγλ", &PositionalXor),
+        Err(Failure::NeitherPosition)
+    );
+    assert_eq!(check("4-9 λ: αβγλ", &PositionalXor), Ok(()));
+}
+
+#[test]
+fn distinct_characters_are_not_distinct_bytes() {
+    assert_eq!(check("1-1 x: ααβ", &MinDistinct(2)), Ok(()));
+    assert_eq!(
+        check("1-1 x: ααβ", &MinDistinct(3)),
+        Err(Failure::TooFewDistinct)
+    );
+}
+
+#[test]
+fn character_classes_take_unicode_ranges() {
+    let cyrillic = CharacterClass::parse("[а-яё]").unwrap();
+    assert!(cyrillic.contains('ё'));
+    assert!(!cyrillic.contains('e'));
+    assert_eq!(check("1-1 п: привёт", &cyrillic), Ok(()));
+    assert_eq!(
+        check("1-1 п: привет!", &cyrillic),
+        Err(Failure::OutsideClass)
+    );
+
+    let not_cjk = CharacterClass::parse("[^一-鿿]").unwrap();
+    assert_eq!(check("1-1 a: abc-äöü", &not_cjk), Ok(()));
+    assert_eq!(
+        check("1-1 a: abc日本", &not_cjk),
+        Err(Failure::OutsideClass)
+    );
+}
+
+#[test]
+fn ascii_passwords_give_the_same_answers() {
+    let entries = parse("1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n".as_bytes()).unwrap();
+    let valid = |policy: &dyn Policy| entries.iter().filter(|e| policy.is_valid(e)).count();
+    assert_eq!(valid(&CountRange), 2);
+    assert_eq!(valid(&PositionalXor), 1);
+}
+
+#[test]
+fn invalid_utf8_is_a_syntax_error() {
+    match parse(&b"1-3 a: ab\xffcd\n"[..]) {
+        Err(ParseError::Syntax(err)) => {
+            assert_eq!((err.line, err.column), (1, 10));
+            assert_eq!(err.found, b"\xff");
+            assert_eq!(err.expected, "UTF-8 text");
+        }
+        other => panic!("expected a syntax error, got {:?}", other),
+    }
+}
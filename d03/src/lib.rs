@@ -1,121 +1,181 @@
-use std::convert::From;
-use std::io::BufRead;
+use common::{BitGrid, LineReader, ParseError, Row, Solution};
+use std::convert::TryFrom;
+use std::io::{self, BufRead, Write};
 
-#[derive(Debug)]
-pub struct Topology {
-    grid: Vec<bool>,
-    width: usize,
-}
-
-impl From<&[u8]> for Topology {
-    fn from(buffer: &[u8]) -> Topology {
-        let mut width = None;
-        let mut grid = Vec::with_capacity(buffer.len());
-        let mut iter = buffer.iter();
-        for c in &mut iter {
-            match c {
-                35 => grid.push(true),
-                46 => grid.push(false),
-                _ => {
-                    width = Some(grid.len());
-                    break;
-                }
-            }
-        }
-        for c in &mut iter {
-            match c {
-                35 => grid.push(true),
-                46 => grid.push(false),
-                _ => (),
-            }
-        }
-        Topology {
-            width: width.unwrap_or(grid.len()),
-            grid,
-        }
-    }
-}
-
-impl Topology {
-    fn get_row(&self, y: usize) -> Option<&[bool]> {
-        let end = self.width * (y + 1);
-        if self.grid.len() >= end {
-            Some(&self.grid[end - self.width..end])
-        } else {
-            None
-        }
-    }
-}
+/// The map of open squares (off) and trees (on).
+pub type Topology = BitGrid;
 
 struct Slope {
     shift_by: usize,
+    down: usize,
     pos: usize,
     count: usize,
 }
 
 impl Slope {
-    fn new(right: usize) -> Slope {
-        Slope {
-            shift_by: right,
-            pos: right,
-            count: 0,
+    // a step to the left is the same as a step to the right by the rest of
+    // the width, so `shift_by` is always a step to the right; a slope that
+    // does not go down never reaches the bottom, so there is none
+    fn new(right: isize, down: usize, width: usize) -> Option<Slope> {
+        if down == 0 {
+            return None;
         }
+        let shift_by = right.rem_euclid(width.max(1) as isize) as usize;
+        Some(Slope {
+            shift_by,
+            down,
+            pos: shift_by,
+            count: 0,
+        })
     }
-    fn visit(&mut self, row: &[bool]) {
-        self.count += row[self.pos] as usize;
+    // returns the column visited on this row, if the slope reaches it
+    fn visit(&mut self, y: usize, row: Row) -> Option<usize> {
+        if !y.is_multiple_of(self.down) {
+            return None;
+        }
+        let visited = self.pos;
+        self.count += row.get(visited) as usize;
         self.pos += self.shift_by;
         if self.pos >= row.len() {
             self.pos -= row.len();
         }
+        Some(visited)
     }
 }
 
-pub fn parse<R>(mut reader: R) -> Option<Topology>
+pub fn parse<R>(reader: R) -> Result<Topology, ParseError>
 where
     R: BufRead,
 {
-    let mut buffer: Vec<u8> = Vec::new();
-    if reader.read_to_end(&mut buffer).is_ok() {
-        Some(Topology::from(buffer.as_slice()))
-    } else {
-        None
-    }
+    let buffer = LineReader::new(reader).read_to_end()?;
+    Topology::try_from(buffer.as_slice())
 }
 
-pub fn p1_solve(topology: &Topology, right: usize) -> usize {
-    let mut s1 = Slope::new(right);
+/// Counts the trees hit going `right` and `down` at a time from the top left
+/// corner until the bottom. The map repeats to the right, so a negative
+/// `right` wraps around from the left edge. Returns `None` if `down` is 0.
+pub fn count_trees(topology: &Topology, right: isize, down: usize) -> Option<usize> {
+    count_trees_batch(topology, &[(right, down)])[0]
+}
+
+/// Counts the trees hit on each of the `(right, down)` slopes, going over the
+/// rows of the map only once. Slopes with `down` 0 count as `None`.
+pub fn count_trees_batch(topology: &Topology, slopes: &[(isize, usize)]) -> Vec<Option<usize>> {
+    let mut slopes: Vec<Option<Slope>> = slopes
+        .iter()
+        .map(|(right, down)| Slope::new(*right, *down, topology.width()))
+        .collect();
 
     let mut y = 1;
 
-    while let Some(row) = topology.get_row(y) {
-        s1.visit(row);
+    while let Some(row) = topology.row(y) {
+        for slope in slopes.iter_mut().flatten() {
+            slope.visit(y, row);
+        }
         y += 1;
     }
 
-    s1.count
+    slopes
+        .iter()
+        .map(|slope| slope.as_ref().map(|slope| slope.count))
+        .collect()
 }
 
-pub fn p2_solve(topology: &Topology) -> usize {
-    let mut s1 = Slope::new(1);
-    let mut s2 = Slope::new(3);
-    let mut s3 = Slope::new(5);
-    let mut s4 = Slope::new(7);
-    let mut s5 = Slope::new(1);
-
-    let mut y = 1;
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Goal {
+    FewestTrees,
+    MostTrees,
+}
 
-    while let Some(row) = topology.get_row(y) {
-        s1.visit(row);
-        s2.visit(row);
-        s3.visit(row);
-        s4.visit(row);
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SlopeTrees {
+    pub right: isize,
+    pub down: usize,
+    pub trees: usize,
+}
 
-        if (y & 1) == 0 {
-            s5.visit(row);
+/// Tries every slope that goes at most `bound` to either side and 1 to
+/// `bound` down, and returns the one that hits the fewest or the most trees.
+/// Of equally good slopes, the one going down the least wins, then the one
+/// furthest to the left.
+pub fn find_slope(topology: &Topology, bound: usize, goal: Goal) -> Option<SlopeTrees> {
+    let bound = bound as isize;
+    let slopes: Vec<(isize, usize)> = (1..=bound)
+        .flat_map(|down| (-bound..=bound).map(move |right| (right, down as usize)))
+        .collect();
+    let trees = count_trees_batch(topology, &slopes);
+
+    let mut best: Option<SlopeTrees> = None;
+    // every slope goes down at least 1, so all of them have a count
+    for ((right, down), trees) in slopes.into_iter().zip(trees.into_iter().flatten()) {
+        let better = match (best, goal) {
+            (None, _) => true,
+            (Some(best), Goal::FewestTrees) => trees < best.trees,
+            (Some(best), Goal::MostTrees) => trees > best.trees,
+        };
+        if better {
+            best = Some(SlopeTrees { right, down, trees });
         }
+    }
+    best
+}
 
+/// Writes the map with the cells visited on the way down marked as in the
+/// puzzle: `O` on open ground and `X` on a tree. The map is drawn once, with
+/// the path wrapping around its sides. `down` must be at least 1.
+pub fn render_path<W: Write>(
+    topology: &Topology,
+    right: isize,
+    down: usize,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut slope = Slope::new(right, down, topology.width())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "down must be at least 1"))?;
+    let mut line = Vec::with_capacity(topology.width() + 1);
+
+    let mut y = 0;
+
+    while let Some(row) = topology.row(y) {
+        line.clear();
+        line.extend(row.iter().map(|tree| if tree { b'#' } else { b'.' }));
+        // the top left corner is where the path starts, not a visit
+        let visited = if y > 0 { slope.visit(y, row) } else { None };
+        if let Some(x) = visited {
+            line[x] = if row.get(x) { b'X' } else { b'O' };
+        }
+        line.push(b'\n');
+        out.write_all(&line)?;
         y += 1;
     }
 
-    s1.count * s2.count * s3.count * s4.count * s5.count
+    Ok(())
+}
+
+pub fn p1_solve(topology: &Topology, right: usize) -> Option<usize> {
+    count_trees(topology, right as isize, 1)
+}
+
+pub fn p2_solve(topology: &Topology) -> Option<usize> {
+    count_trees_batch(topology, &[(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)])
+        .into_iter()
+        .product()
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Topology;
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input, 3)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
 }
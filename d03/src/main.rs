@@ -1,26 +1,140 @@
 extern crate d03;
-use d03::{p1_solve, p2_solve, parse};
-use std::{env, io};
+use d03::{find_slope, p1_solve, p2_solve, parse, render_path, Goal, Topology};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+use std::{env, io, process};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+const USAGE: &str = "\
+usage: d03 <part> < input.txt
+       d03 best [--bound <bound>] [--most] < input.txt
+       d03 render [--right <right>] [--down <down>] [--output <path>] < input.txt";
+
+enum Command {
+    Part(u8),
+    Best {
+        bound: usize,
+        goal: Goal,
+    },
+    Render {
+        right: isize,
+        down: usize,
+        output: Option<String>,
+    },
+}
 
-    let part = args
-        .get(1)
-        .expect("Missing argument 'part'")
+fn value<'a, I, T>(iter: &mut I, flag: &str) -> Result<T, String>
+where
+    I: Iterator<Item = &'a String>,
+    T: FromStr,
+{
+    iter.next()
+        .ok_or(format!("{} requires a value", flag))?
         .trim()
         .parse()
-        .expect("Part must be a number");
+        .map_err(|_| format!("invalid value for {}", flag))
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut iter = args.iter();
+    let mut command = match iter.next().map(String::as_str) {
+        Some("best") => Command::Best {
+            bound: 10,
+            goal: Goal::FewestTrees,
+        },
+        Some("render") => Command::Render {
+            right: 3,
+            down: 1,
+            output: None,
+        },
+        Some(part) => Command::Part(
+            part.trim()
+                .parse()
+                .map_err(|_| format!("unknown command '{}'", part))?,
+        ),
+        None => return Err(String::from("missing part or command")),
+    };
+
+    while let Some(arg) = iter.next() {
+        match (&mut command, arg.as_str()) {
+            (Command::Best { bound, .. }, "--bound") => *bound = value(&mut iter, arg)?,
+            (Command::Best { goal, .. }, "--most") => *goal = Goal::MostTrees,
+            (Command::Render { right, .. }, "--right") => *right = value(&mut iter, arg)?,
+            (Command::Render { down, .. }, "--down") => *down = value(&mut iter, arg)?,
+            (Command::Render { output, .. }, "--output") => *output = Some(value(&mut iter, arg)?),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    if let Command::Render { down: 0, .. } = command {
+        return Err(String::from("--down must be at least 1"));
+    }
+    Ok(command)
+}
 
-    if let Some(topology) = parse(io::stdin().lock()) {
-        let result = match part {
-            1 => Some(p1_solve(&topology, 3)),
-            2 => Some(p2_solve(&topology)),
-            _ => None,
-        };
+fn render(
+    topology: &Topology,
+    right: isize,
+    down: usize,
+    output: Option<String>,
+) -> io::Result<()> {
+    match output {
+        Some(path) => {
+            let mut file = BufWriter::new(File::create(path)?);
+            render_path(topology, right, down, &mut file)?;
+            file.flush()
+        }
+        None => render_path(topology, right, down, &mut io::stdout().lock()),
+    }
+}
 
-        if let Some(result) = result {
-            println!("{}", result);
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let topology = match parse(io::stdin().lock()) {
+        Ok(topology) => topology,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    match command {
+        Command::Part(part) => {
+            let result = match part {
+                1 => p1_solve(&topology, 3),
+                2 => p2_solve(&topology),
+                _ => None,
+            };
+            if let Some(result) = result {
+                println!("{}", result);
+            }
+        }
+        Command::Best { bound, goal } => {
+            if let Some(best) = find_slope(&topology, bound, goal) {
+                println!(
+                    "right {}, down {}: {} trees",
+                    best.right, best.down, best.trees
+                );
+            }
+        }
+        Command::Render {
+            right,
+            down,
+            output,
+        } => {
+            if let Err(err) = render(&topology, right, down, output) {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
         }
     }
 }
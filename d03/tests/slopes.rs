@@ -0,0 +1,96 @@
+use d03::{
+    count_trees, count_trees_batch, find_slope, parse, render_path, Goal, SlopeTrees, Topology,
+};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind};
+
+fn sample() -> Topology {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/sample.txt")).unwrap();
+    parse(BufReader::new(file)).unwrap()
+}
+
+fn slope(right: isize, down: usize, trees: usize) -> Option<SlopeTrees> {
+    Some(SlopeTrees { right, down, trees })
+}
+
+#[test]
+fn counts_the_puzzle_slopes() {
+    let topology = sample();
+    let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    let trees: Vec<Option<usize>> = [2, 7, 3, 4, 2].iter().map(|t| Some(*t)).collect();
+    assert_eq!(count_trees_batch(&topology, &slopes), trees);
+}
+
+// counted by hand on sample.txt
+#[test]
+fn counts_slopes_going_left_or_far_down() {
+    let topology = sample();
+    // columns 10, 9, 8, ... 1 on rows 1 to 10
+    assert_eq!(count_trees(&topology, -1, 1), Some(5));
+    // columns 8, 5, 2, 10, 7 on rows 2, 4, 6, 8, 10
+    assert_eq!(count_trees(&topology, -3, 2), Some(1));
+    // columns 1, 2, 3 on rows 3, 6, 9
+    assert_eq!(count_trees(&topology, 1, 3), Some(0));
+    // a full width to the left is the same as going straight down
+    assert_eq!(count_trees(&topology, -11, 1), count_trees(&topology, 0, 1));
+}
+
+#[test]
+fn slopes_that_do_not_go_down_have_no_count() {
+    let topology = sample();
+    assert_eq!(count_trees(&topology, 3, 0), None);
+    assert_eq!(
+        count_trees_batch(&topology, &[(3, 1), (3, 0)]),
+        vec![Some(7), None]
+    );
+
+    let err = render_path(&topology, 3, 0, &mut Vec::new()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+// the puzzle's drawing of the path 3 right and 1 down, wrapped onto one copy
+// of the map
+#[test]
+fn renders_the_puzzle_path() {
+    let mut out = Vec::new();
+    render_path(&sample(), 3, 1, &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "..##.......\n\
+         #..O#...#..\n\
+         .#....X..#.\n\
+         ..#.#...#O#\n\
+         .X...##..#.\n\
+         ..#.X#.....\n\
+         .#.#.#.O..#\n\
+         .#........X\n\
+         #.X#...#...\n\
+         #...#X....#\n\
+         .#..#...X.#\n"
+    );
+}
+
+#[test]
+fn finds_the_best_slope_on_the_sample() {
+    let topology = sample();
+    assert_eq!(find_slope(&topology, 1, Goal::FewestTrees), slope(1, 1, 2));
+    assert_eq!(find_slope(&topology, 1, Goal::MostTrees), slope(-1, 1, 5));
+}
+
+#[test]
+fn ties_go_to_the_least_down_then_the_leftmost() {
+    let open: Topology = parse("...\n...\n...\n".as_bytes()).unwrap();
+    assert_eq!(find_slope(&open, 2, Goal::FewestTrees), slope(-2, 1, 0));
+    assert_eq!(find_slope(&open, 2, Goal::MostTrees), slope(-2, 1, 0));
+
+    // going down 1 hits two trees and going down 2 only one
+    let trees: Topology = parse("###\n###\n###\n".as_bytes()).unwrap();
+    assert_eq!(find_slope(&trees, 2, Goal::FewestTrees), slope(-2, 2, 1));
+    assert_eq!(find_slope(&trees, 2, Goal::MostTrees), slope(-2, 1, 2));
+}
+
+#[test]
+fn a_bound_of_zero_has_no_slopes() {
+    assert_eq!(find_slope(&sample(), 0, Goal::FewestTrees), None);
+    assert_eq!(find_slope(&sample(), 0, Goal::MostTrees), None);
+}
@@ -1,173 +1,97 @@
 mod passports;
+mod schema;
+mod valid;
 
-use passports::Passports;
-use std::io::BufRead;
-use std::str;
+use common::{LineReader, ParseError, Solution};
+pub use passports::{Passport, Passports};
+pub use schema::{Field, HeightUnit, InvalidField, PassportSchema, Reason, Report, Validator};
+use serde::Serialize;
+use std::io::{self, BufRead, Write};
+pub use valid::{Height, ValidPassport};
 
-pub fn parse<R>(mut reader: R) -> Passports
+fn parse_line(line: &[u8], line_no: usize, passports: &mut Passports) -> Result<(), ParseError> {
+    let mut column = 1;
+    for field in line.split(|c| *c == b' ') {
+        match field.iter().position(|c| *c == b':') {
+            Some(0) => return Err(ParseError::syntax(line_no, column, field, "field name")),
+            Some(colon) => passports.push_field(line_no, &field[..colon], &field[colon + 1..]),
+            None if field.is_empty() => (),
+            None => return Err(ParseError::syntax(line_no, column, field, "key:value pair")),
+        }
+        column += field.len() + 1;
+    }
+    Ok(())
+}
+
+pub fn parse<R>(reader: R) -> Result<Passports, ParseError>
 where
     R: BufRead,
 {
-    let mut lines: Vec<u8> = Vec::new();
-    let mut buf: Vec<u8> = Vec::new();
-    let mut pad = false;
+    let mut passports = Passports::new();
+    let mut input = LineReader::new(reader);
+    // whether the last line belonged to a passport
+    let mut in_passport = false;
 
-    while let Ok(n) = reader.read_until(b'\n', &mut buf) {
-        match n {
-            0 => break,
-            1 => {
-                lines.append(&mut buf);
-                pad = false;
-            }
-            _n => {
-                if pad {
-                    lines.push(b' ');
-                }
-                if buf[buf.len() - 1] == b'\n' {
-                    buf.pop();
-                }
-                lines.append(&mut buf);
-                pad = true;
+    while let Some((line_no, line)) = input.next_line()? {
+        if line.is_empty() {
+            in_passport = false;
+        } else {
+            if !in_passport {
+                passports.begin(line_no);
+                in_passport = true;
             }
+            parse_line(line, line_no, &mut passports)?;
         }
     }
 
-    Passports::new(lines)
+    Ok(passports)
 }
 
-pub fn p1_solve(passports: &Passports) -> usize {
-    let mut i = 0;
-    let mut fields = 0;
-    for passport in passports.iter() {
-        for (key, _value) in passport.iter() {
-            match key {
-                b"byr" => fields |= 1,
-                b"iyr" => fields |= 2,
-                b"eyr" => fields |= 4,
-                b"hgt" => fields |= 8,
-                b"hcl" => fields |= 16,
-                b"ecl" => fields |= 32,
-                b"pid" => fields |= 64,
-                _ => (),
-            }
-        }
-        i += (fields == 127) as usize;
-        fields = 0
+/// Writes each item as JSON on a line of its own.
+pub fn write_json_lines<W, I>(mut writer: W, items: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    for item in items {
+        serde_json::to_writer(&mut writer, &item)?;
+        writer.write_all(b"\n")?;
     }
-    i
+    Ok(())
 }
 
-macro_rules! is_hex {
-    ($e:expr) => {
-        (is_digit!($e) || ($e >= b'a' && $e <= b'f'))
-    };
+pub fn p1_solve(passports: &Passports) -> usize {
+    let schema = PassportSchema::standard();
+    passports
+        .iter()
+        .filter(|passport| schema.has_required_fields(passport))
+        .count()
 }
 
-macro_rules! is_digit {
-    ($e:expr) => {
-        ($e >= b'0' && $e <= b'9')
-    };
+pub fn p2_solve(passports: &Passports) -> usize {
+    let schema = PassportSchema::standard();
+    passports
+        .iter()
+        .filter(|passport| schema.is_valid(passport))
+        .count()
 }
 
-pub fn p2_solve(passports: &Passports) -> usize {
-    let mut i = 0;
-    let mut fields = 0;
-    let mut len;
-    for passport in passports.iter() {
-        for (key, value) in passport.iter() {
-            match key {
-                b"byr" => {
-                    if let Some(byr) = str::from_utf8(value)
-                        .ok()
-                        .and_then(|str| str.parse::<u16>().ok())
-                    {
-                        if byr >= 1920 && byr <= 2002 {
-                            fields |= 1;
-                        }
-                    }
-                }
-                b"iyr" => {
-                    if let Some(iyr) = str::from_utf8(value)
-                        .ok()
-                        .and_then(|str| str.parse::<u16>().ok())
-                    {
-                        if iyr >= 2010 && iyr <= 2020 {
-                            fields |= 2
-                        }
-                    }
-                }
-                b"eyr" => {
-                    if let Some(eyr) = str::from_utf8(value)
-                        .ok()
-                        .and_then(|str| str.parse::<u16>().ok())
-                    {
-                        if eyr >= 2020 && eyr <= 2030 {
-                            fields |= 4
-                        }
-                    }
-                }
-                b"hgt" => {
-                    len = value.len();
-                    if value.len() > 2 {
-                        if let Some(hgt) = str::from_utf8(&value[0..len - 2])
-                            .ok()
-                            .and_then(|str| str.parse::<u8>().ok())
-                        {
-                            match &value[len - 2..] {
-                                b"cm" => {
-                                    if hgt >= 150 && hgt <= 193 {
-                                        fields |= 8
-                                    }
-                                }
-                                b"in" => {
-                                    if hgt >= 59 && hgt <= 76 {
-                                        fields |= 8
-                                    }
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                }
-                b"hcl" => {
-                    len = value.len();
-                    if len == 7
-                        && &value[..1] == b"#"
-                        && is_hex!(value[1])
-                        && is_hex!(value[2])
-                        && is_hex!(value[3])
-                        && is_hex!(value[4])
-                        && is_hex!(value[5])
-                        && is_hex!(value[6])
-                    {
-                        fields |= 16
-                    }
-                }
-                b"ecl" => match value {
-                    b"amb" | b"blu" | b"brn" | b"gry" | b"grn" | b"hzl" | b"oth" => fields |= 32,
-                    _ => (),
-                },
-                b"pid" => {
-                    len = value.len();
-                    if len == 9
-                        && is_digit!(value[0])
-                        && is_digit!(value[1])
-                        && is_digit!(value[2])
-                        && is_digit!(value[3])
-                        && is_digit!(value[4])
-                        && is_digit!(value[5])
-                        && is_digit!(value[6])
-                        && is_digit!(value[7])
-                        && is_digit!(value[8])
-                    {
-                        fields |= 64;
-                    }
-                }
-                _ => (),
-            }
-        }
-        i += (fields == 127) as usize;
-        fields = 0
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Passports;
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        Some(p1_solve(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(p2_solve(input))
     }
-    i
 }
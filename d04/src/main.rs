@@ -1,26 +1,167 @@
 extern crate d04;
-use d04::{p1_solve, p2_solve, parse};
-use std::{env, io};
+use d04::{parse, write_json_lines, PassportSchema, Report};
+use std::fs::File;
+use std::io::BufReader;
+use std::{env, io, process};
+
+const USAGE: &str = "usage: d04 <part> [--schema <path>] [--explain | --export] < input.txt";
+
+enum Output {
+    Count,
+    Explain,
+    Export,
+}
+
+struct Options {
+    part: u8,
+    schema: Option<String>,
+    output: Output,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut iter = args.iter();
+    let part = match iter.next().map(|part| part.trim()) {
+        Some("1") => 1,
+        Some("2") => 2,
+        Some(part) => return Err(format!("invalid part: {}", part)),
+        None => return Err(String::from("missing part")),
+    };
+
+    let mut schema = None;
+    let mut output = Output::Count;
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--schema" => {
+                schema = Some(
+                    iter.next()
+                        .ok_or(format!("{} requires a value", arg))?
+                        .clone(),
+                )
+            }
+            "--explain" | "--export" => {
+                if let Output::Count = output {
+                    output = match arg.as_str() {
+                        "--explain" => Output::Explain,
+                        _ => Output::Export,
+                    };
+                } else {
+                    return Err(String::from("--explain and --export cannot be combined"));
+                }
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Options {
+        part,
+        schema,
+        output,
+    })
+}
+
+fn load_schema(path: &str) -> Result<PassportSchema, String> {
+    let file = File::open(path).map_err(|err| format!("{}: {}", path, err))?;
+    PassportSchema::from_json(BufReader::new(file)).map_err(|err| format!("{}: {}", path, err))
+}
+
+// part 1 only asks for the fields to be there, so it leaves out their values
+fn explain(report: &Report, part: u8) {
+    let lines = report.lines.clone();
+    let lines = if lines.start() == lines.end() {
+        format!("line {}", lines.start())
+    } else {
+        format!("lines {}-{}", lines.start(), lines.end())
+    };
+    let valid = match part {
+        1 => report.is_complete(),
+        _ => report.is_valid(),
+    };
+    println!("{}: {}", lines, if valid { "valid" } else { "invalid" });
+
+    for key in &report.missing {
+        println!("  missing {}", key);
+    }
+    if part != 1 {
+        for field in &report.invalid {
+            println!("  {}", field);
+        }
+    }
+    for key in &report.duplicate {
+        println!("  duplicate {}", String::from_utf8_lossy(key));
+    }
+    for key in &report.unknown {
+        println!("  unknown {}", String::from_utf8_lossy(key));
+    }
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    let part = args
-        .get(1)
-        .expect("Missing argument 'part'")
-        .trim()
-        .parse()
-        .expect("Part must be a number");
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
 
-    let passports = parse(io::stdin().lock());
+    let schema = match &options.schema {
+        Some(path) => match load_schema(path) {
+            Ok(schema) => schema,
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        },
+        None => PassportSchema::standard(),
+    };
 
-    let result = match part {
-        1 => Some(p1_solve(&passports)),
-        2 => Some(p2_solve(&passports)),
-        _ => None,
+    let passports = match parse(io::stdin().lock()) {
+        Ok(passports) => passports,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
     };
 
-    if let Some(result) = result {
-        println!("{}", result);
+    match options.output {
+        Output::Count => {
+            let count = passports
+                .iter()
+                .filter(|passport| match options.part {
+                    1 => schema.has_required_fields(passport),
+                    _ => schema.is_valid(passport),
+                })
+                .count();
+            println!("{}", count);
+        }
+        Output::Explain => {
+            for passport in passports.iter() {
+                explain(&schema.explain(&passport), options.part);
+            }
+        }
+        // part 1 exports the passports as they were written, part 2 reads
+        // them into their types
+        Output::Export => {
+            let stdout = io::stdout();
+            let written = match options.part {
+                1 => write_json_lines(
+                    stdout.lock(),
+                    passports
+                        .iter()
+                        .filter(|passport| schema.has_required_fields(passport)),
+                ),
+                _ => write_json_lines(
+                    stdout.lock(),
+                    passports
+                        .iter()
+                        .filter_map(|passport| schema.validate(&passport).ok()),
+                ),
+            };
+            if let Err(err) = written {
+                eprintln!("{}", err);
+                process::exit(1);
+            }
+        }
     }
 }
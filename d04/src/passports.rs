@@ -1,103 +1,121 @@
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::ops::{Range, RangeInclusive};
+
+// where the key and value of a field are in the buffer
+#[derive(Clone, Debug)]
+struct Span {
+    key: Range<usize>,
+    value: Range<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct Record {
+    first_line: usize,
+    last_line: usize,
+    fields: Range<usize>,
+}
+
+/// Passports read from the input. The keys and values of every passport are
+/// kept in one buffer, and a passport only knows where its own fields are.
+#[derive(Clone, Debug, Default)]
 pub struct Passports {
     buffer: Vec<u8>,
+    fields: Vec<Span>,
+    records: Vec<Record>,
 }
 
 impl Passports {
-    pub fn new(buffer: Vec<u8>) -> Self {
-        Passports { buffer }
+    pub fn new() -> Self {
+        Passports::default()
     }
 
-    pub fn iter(&self) -> PassportCollectionIterator {
-        PassportCollectionIterator {
-            passports: &self,
-            i: 0,
-            start: 0,
-        }
+    /// Starts a new passport, written from input line `line` on.
+    pub fn begin(&mut self, line: usize) {
+        let start = self.fields.len();
+        self.records.push(Record {
+            first_line: line,
+            last_line: line,
+            fields: start..start,
+        });
     }
-}
 
-pub struct PassportCollectionIterator<'a> {
-    passports: &'a Passports,
-    i: usize,
-    start: usize,
-}
+    /// Adds a field, written on input line `line`, to the last passport.
+    ///
+    /// Panics if no passport has been started.
+    pub fn push_field(&mut self, line: usize, key: &[u8], value: &[u8]) {
+        let record = self
+            .records
+            .last_mut()
+            .expect("a field must belong to a passport");
+        let start = self.buffer.len();
+        self.buffer.extend_from_slice(key);
+        self.buffer.extend_from_slice(value);
+        self.fields.push(Span {
+            key: start..start + key.len(),
+            value: start + key.len()..self.buffer.len(),
+        });
+        record.last_line = line;
+        record.fields.end = self.fields.len();
+    }
 
-impl<'a> Iterator for PassportCollectionIterator<'a> {
-    type Item = Passport<'a>;
-    fn next(&mut self) -> Option<Passport<'a>> {
-        let buffer: &'a [u8];
-        self.start = self.i;
-        for c in &self.passports.buffer[self.i..] {
-            if *c == b'\n' {
-                buffer = &self.passports.buffer[self.start..self.i];
-                self.i += 1;
-                return Some(Passport { buffer });
-            }
-            self.i += 1;
-        }
-        if self.i > self.start {
-            return Some(Passport {
-                buffer: &self.passports.buffer[self.start..self.i],
-            });
-        }
-        None
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Passport<'_>> {
+        self.records.iter().map(move |record| Passport {
+            buffer: &self.buffer,
+            fields: &self.fields[record.fields.clone()],
+            lines: record.first_line..=record.last_line,
+        })
     }
 }
 
-#[derive(Debug)]
+/// One passport, borrowed from `Passports`.
+///
+/// It serializes as a map from keys to values, in input order. A repeated
+/// key is written as many times as it appears.
+#[derive(Clone, Debug)]
 pub struct Passport<'a> {
     buffer: &'a [u8],
+    fields: &'a [Span],
+    lines: RangeInclusive<usize>,
 }
 
-pub struct PassportIterator<'a> {
-    passport: &'a Passport<'a>,
-    i: usize,
-    start: usize,
-}
+impl<'a> Passport<'a> {
+    /// The input lines the passport was written on.
+    pub fn lines(&self) -> RangeInclusive<usize> {
+        self.lines.clone()
+    }
 
-impl Passport<'_> {
-    pub fn iter(&self) -> PassportIterator {
-        PassportIterator {
-            passport: self,
-            i: 0,
-            start: 0,
-        }
+    pub fn iter(&self) -> impl Iterator<Item = (&'a [u8], &'a [u8])> {
+        let buffer = self.buffer;
+        self.fields
+            .iter()
+            .map(move |span| (&buffer[span.key.clone()], &buffer[span.value.clone()]))
     }
-}
 
-impl<'a> Iterator for PassportIterator<'a> {
-    type Item = (&'a [u8], &'a [u8]);
-    fn next(&mut self) -> Option<(&'a [u8], &'a [u8])> {
-        if self.i > self.passport.buffer.len() {
-            return None;
-        }
-        self.start = self.i;
-        let mut key = None;
-        let mut value = None;
+    /// The value of the first field with the key, if there is one.
+    pub fn get(&self, key: &[u8]) -> Option<&'a [u8]> {
+        self.iter()
+            .find(|(field, _)| *field == key)
+            .map(|(_, value)| value)
+    }
+}
 
-        for c in &self.passport.buffer[self.i..] {
-            if *c == b':' {
-                key = Some(&self.passport.buffer[self.start..self.i]);
-                break;
-            }
-            self.i += 1;
-        }
-        self.i += 1;
-        if self.i > self.passport.buffer.len() {
-            return None;
-        }
-        self.start = self.i;
-        for c in &self.passport.buffer[self.start..] {
-            if *c == b' ' {
-                value = Some(&self.passport.buffer[self.start..self.i]);
-                break;
-            }
-            self.i += 1;
-        }
-        if value.is_none() && self.i > self.start {
-            value = Some(&self.passport.buffer[self.start..self.i]);
+impl Serialize for Passport<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(
+                &String::from_utf8_lossy(key),
+                &String::from_utf8_lossy(value),
+            )?;
         }
-        self.i += 1;
-        key.and_then(|k| value.map(|v| (k, v)))
+        map.end()
     }
 }
@@ -0,0 +1,384 @@
+use crate::passports::Passport;
+use serde::de::Error;
+use serde::Deserialize;
+use std::fmt;
+use std::io::Read;
+use std::ops::RangeInclusive;
+
+// fields are tracked as bits of a u64 while a passport is checked
+const MAX_FIELDS: usize = 64;
+
+/// How the value of a field is checked.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Validator {
+    /// Any value will do.
+    Any,
+    /// Four digits, from `min` to `max`.
+    Year { min: u16, max: u16 },
+    /// A number followed by one of the units, in that unit's range.
+    Height(Vec<HeightUnit>),
+    /// `#` followed by six lowercase hexadecimal digits.
+    HexColour,
+    /// One of the listed values.
+    OneOf(Vec<String>),
+    /// Exactly this many digits.
+    Digits(usize),
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct HeightUnit {
+    pub unit: String,
+    pub min: u16,
+    pub max: u16,
+}
+
+// a number written in decimal digits only, without a sign
+pub fn number(digits: &[u8]) -> Option<u16> {
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u16, |number, c| {
+        if c.is_ascii_digit() {
+            number.checked_mul(10)?.checked_add((c - b'0') as u16)
+        } else {
+            None
+        }
+    })
+}
+
+/// Why a validator turned down a value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Reason {
+    NotYear,
+    OutOfRange { min: u16, max: u16 },
+    WithoutUnit,
+    UnknownUnit,
+    NotNumber,
+    NotHexColour,
+    NotInSet,
+    NotDigits(usize),
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::NotYear => f.write_str("not a four-digit year"),
+            Reason::OutOfRange { min, max } => write!(f, "not between {} and {}", min, max),
+            Reason::WithoutUnit => f.write_str("without unit"),
+            Reason::UnknownUnit => f.write_str("with an unknown unit"),
+            Reason::NotNumber => f.write_str("not a number"),
+            Reason::NotHexColour => f.write_str("not a hex colour"),
+            Reason::NotInSet => f.write_str("not in set"),
+            Reason::NotDigits(len) => write!(f, "not {} digits", len),
+        }
+    }
+}
+
+fn in_range(number: u16, min: u16, max: u16) -> Result<(), Reason> {
+    if (min..=max).contains(&number) {
+        Ok(())
+    } else {
+        Err(Reason::OutOfRange { min, max })
+    }
+}
+
+impl Validator {
+    pub fn check(&self, value: &[u8]) -> Result<(), Reason> {
+        match self {
+            Validator::Any => Ok(()),
+            Validator::Year { min, max } => match number(value) {
+                Some(year) if value.len() == 4 => in_range(year, *min, *max),
+                _ => Err(Reason::NotYear),
+            },
+            Validator::Height(units) => {
+                if value.is_empty() {
+                    return Err(Reason::NotNumber);
+                } else if value.iter().all(u8::is_ascii_digit) {
+                    return Err(Reason::WithoutUnit);
+                }
+                let (unit, height) = units
+                    .iter()
+                    .find_map(|unit| Some((unit, value.strip_suffix(unit.unit.as_bytes())?)))
+                    .ok_or(Reason::UnknownUnit)?;
+                let height = number(height).ok_or(Reason::NotNumber)?;
+                in_range(height, unit.min, unit.max)
+            }
+            Validator::HexColour => {
+                if value.len() == 7
+                    && value[0] == b'#'
+                    && value[1..]
+                        .iter()
+                        .all(|c| matches!(c, b'0'..=b'9' | b'a'..=b'f'))
+                {
+                    Ok(())
+                } else {
+                    Err(Reason::NotHexColour)
+                }
+            }
+            Validator::OneOf(values) => {
+                if values.iter().any(|allowed| allowed.as_bytes() == value) {
+                    Ok(())
+                } else {
+                    Err(Reason::NotInSet)
+                }
+            }
+            Validator::Digits(len) => {
+                if value.len() == *len && value.iter().all(u8::is_ascii_digit) {
+                    Ok(())
+                } else {
+                    Err(Reason::NotDigits(*len))
+                }
+            }
+        }
+    }
+
+    pub fn accepts(&self, value: &[u8]) -> bool {
+        self.check(value).is_ok()
+    }
+}
+
+/// A field a passport may have, and how its value is checked.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct Field {
+    pub key: String,
+    pub required: bool,
+    pub validator: Validator,
+}
+
+/// The fields a passport is checked against. Keys the schema does not list
+/// are ignored, and every listed field that is present must have a value its
+/// validator accepts, whether the field is required or not.
+///
+/// A schema is built in code, or read from JSON such as:
+///
+/// ```json
+/// {"fields": [
+///     {"key": "byr", "required": true, "validator": {"year": {"min": 1920, "max": 2002}}},
+///     {"key": "hgt", "required": true, "validator": {"height": [{"unit": "cm", "min": 150, "max": 193}]}},
+///     {"key": "hcl", "required": true, "validator": "hex_colour"},
+///     {"key": "ecl", "required": true, "validator": {"one_of": ["amb", "blu"]}},
+///     {"key": "pid", "required": true, "validator": {"digits": 9}},
+///     {"key": "cid", "required": false, "validator": "any"}
+/// ]}
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PassportSchema {
+    fields: Vec<Field>,
+}
+
+impl PassportSchema {
+    /// A schema without any fields.
+    pub fn new() -> Self {
+        PassportSchema::default()
+    }
+
+    /// The rules passports are checked against in the puzzle.
+    pub fn standard() -> Self {
+        let unit = |unit: &str, min, max| HeightUnit {
+            unit: String::from(unit),
+            min,
+            max,
+        };
+        let colours = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+        PassportSchema::new()
+            .required(
+                "byr",
+                Validator::Year {
+                    min: 1920,
+                    max: 2002,
+                },
+            )
+            .required(
+                "iyr",
+                Validator::Year {
+                    min: 2010,
+                    max: 2020,
+                },
+            )
+            .required(
+                "eyr",
+                Validator::Year {
+                    min: 2020,
+                    max: 2030,
+                },
+            )
+            .required(
+                "hgt",
+                Validator::Height(vec![unit("cm", 150, 193), unit("in", 59, 76)]),
+            )
+            .required("hcl", Validator::HexColour)
+            .required(
+                "ecl",
+                Validator::OneOf(colours.iter().map(|c| String::from(*c)).collect()),
+            )
+            .required("pid", Validator::Digits(9))
+            .optional("cid", Validator::Any)
+    }
+
+    /// Reads a schema written as JSON.
+    pub fn from_json<R: Read>(reader: R) -> serde_json::Result<Self> {
+        let schema: PassportSchema = serde_json::from_reader(reader)?;
+        if schema.fields.len() > MAX_FIELDS {
+            return Err(serde_json::Error::custom(format!(
+                "a schema can have at most {} fields",
+                MAX_FIELDS
+            )));
+        }
+        Ok(schema)
+    }
+
+    /// Adds a field every passport must have.
+    ///
+    /// Panics if the schema already has 64 fields.
+    pub fn required(self, key: &str, validator: Validator) -> Self {
+        self.field(key, true, validator)
+    }
+
+    /// Adds a field a passport may leave out.
+    ///
+    /// Panics if the schema already has 64 fields.
+    pub fn optional(self, key: &str, validator: Validator) -> Self {
+        self.field(key, false, validator)
+    }
+
+    fn field(mut self, key: &str, required: bool, validator: Validator) -> Self {
+        assert!(
+            self.fields.len() < MAX_FIELDS,
+            "a schema can have at most {} fields",
+            MAX_FIELDS
+        );
+        self.fields.push(Field {
+            key: String::from(key),
+            required,
+            validator,
+        });
+        self
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    fn required_mask(&self) -> u64 {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.required)
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    fn position(&self, key: &[u8]) -> Option<usize> {
+        self.fields
+            .iter()
+            .position(|field| field.key.as_bytes() == key)
+    }
+
+    /// Whether the passport has every required field, whatever their values.
+    pub fn has_required_fields(&self, passport: &Passport) -> bool {
+        let required = self.required_mask();
+        let present = passport
+            .iter()
+            .filter_map(|(key, _)| self.position(key))
+            .fold(0, |mask, i| mask | 1 << i);
+        present & required == required
+    }
+
+    /// Whether the passport has every required field, and every field the
+    /// schema lists has a valid value.
+    pub fn is_valid(&self, passport: &Passport) -> bool {
+        let required = self.required_mask();
+        let mut present = 0;
+        for (key, value) in passport.iter() {
+            if let Some(i) = self.position(key) {
+                if !self.fields[i].validator.accepts(value) {
+                    return false;
+                }
+                present |= 1 << i;
+            }
+        }
+        present & required == required
+    }
+
+    /// Everything that is wrong with the passport: the required fields it
+    /// leaves out, the values the schema turns down, and the keys that are
+    /// repeated or that the schema does not list.
+    pub fn explain<'a>(&'a self, passport: &Passport<'a>) -> Report<'a> {
+        let mut report = Report {
+            lines: passport.lines(),
+            missing: Vec::new(),
+            invalid: Vec::new(),
+            duplicate: Vec::new(),
+            unknown: Vec::new(),
+        };
+        let mut present = 0u64;
+        for (key, value) in passport.iter() {
+            let i = match self.position(key) {
+                Some(i) => i,
+                None => {
+                    report.unknown.push(key);
+                    continue;
+                }
+            };
+            if present & 1 << i != 0 && !report.duplicate.contains(&key) {
+                report.duplicate.push(key);
+            }
+            present |= 1 << i;
+            if let Err(reason) = self.fields[i].validator.check(value) {
+                report.invalid.push(InvalidField { key, value, reason });
+            }
+        }
+        report.missing = self
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(i, field)| field.required && present & 1 << i == 0)
+            .map(|(_, field)| field.key.as_str())
+            .collect();
+        report
+    }
+}
+
+/// A value a passport has for a field, and why the schema turned it down.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvalidField<'a> {
+    pub key: &'a [u8],
+    pub value: &'a [u8],
+    pub reason: Reason,
+}
+
+impl fmt::Display for InvalidField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} {}",
+            String::from_utf8_lossy(self.key),
+            String::from_utf8_lossy(self.value),
+            self.reason
+        )
+    }
+}
+
+/// What a schema found wrong with one passport. Repeated and unknown keys are
+/// reported, but do not make the passport invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report<'a> {
+    pub lines: RangeInclusive<usize>,
+    pub missing: Vec<&'a str>,
+    pub invalid: Vec<InvalidField<'a>>,
+    pub duplicate: Vec<&'a [u8]>,
+    pub unknown: Vec<&'a [u8]>,
+}
+
+impl Report<'_> {
+    /// Whether the passport has every required field.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Whether the passport has every required field, all of them valid.
+    pub fn is_valid(&self) -> bool {
+        self.is_complete() && self.invalid.is_empty()
+    }
+}
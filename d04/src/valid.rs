@@ -0,0 +1,111 @@
+use crate::passports::Passport;
+use crate::schema::{number, InvalidField, PassportSchema, Report};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::str;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum Height {
+    #[serde(rename = "cm")]
+    Centimetres(u16),
+    #[serde(rename = "in")]
+    Inches(u16),
+}
+
+/// The fields of a passport that passed validation, read into their types.
+/// It serializes with the passport's own keys.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ValidPassport<'a> {
+    #[serde(rename = "byr")]
+    pub birth_year: u16,
+    #[serde(rename = "iyr")]
+    pub issue_year: u16,
+    #[serde(rename = "eyr")]
+    pub expiration_year: u16,
+    #[serde(rename = "hgt")]
+    pub height: Height,
+    #[serde(rename = "hcl")]
+    pub hair_colour: &'a str,
+    #[serde(rename = "ecl")]
+    pub eye_colour: &'a str,
+    #[serde(rename = "pid")]
+    pub passport_id: &'a str,
+    #[serde(rename = "cid", skip_serializing_if = "Option::is_none")]
+    pub country_id: Option<Cow<'a, str>>,
+}
+
+impl PassportSchema {
+    /// Checks the passport, and reads it into a `ValidPassport` if it is
+    /// valid. Since a `ValidPassport` is made of the fields of the standard
+    /// schema, the passport has to pass those too.
+    pub fn validate<'a>(
+        &'a self,
+        passport: &Passport<'a>,
+    ) -> Result<ValidPassport<'a>, Report<'a>> {
+        let mut report = self.explain(passport);
+        if report.is_valid() {
+            if let Some(valid) = ValidPassport::read(passport, &mut report) {
+                return Ok(valid);
+            }
+        }
+        Err(report)
+    }
+}
+
+impl<'a> ValidPassport<'a> {
+    // Reads the fields of the standard schema, adding the ones that are
+    // missing or invalid to the report.
+    fn read(passport: &Passport<'a>, report: &mut Report<'a>) -> Option<Self> {
+        let standard = PassportSchema::standard();
+        let mut field = |key: &'static str| {
+            let field = standard.fields().iter().find(|field| field.key == key)?;
+            match passport.get(key.as_bytes()) {
+                Some(value) => match field.validator.check(value) {
+                    Ok(()) => Some(value),
+                    Err(reason) => {
+                        report.invalid.push(InvalidField {
+                            key: key.as_bytes(),
+                            value,
+                            reason,
+                        });
+                        None
+                    }
+                },
+                None => {
+                    if field.required {
+                        report.missing.push(key);
+                    }
+                    None
+                }
+            }
+        };
+        // every field is looked at before giving up, so that all of them
+        // make it to the report
+        let byr = field("byr");
+        let iyr = field("iyr");
+        let eyr = field("eyr");
+        let hgt = field("hgt");
+        let hcl = field("hcl");
+        let ecl = field("ecl");
+        let pid = field("pid");
+        let cid = field("cid");
+
+        // the values passed their validators, so they can be read as such
+        let text = |value| str::from_utf8(value).ok();
+        let height = hgt.and_then(|hgt| match hgt.split_at(hgt.len() - 2) {
+            (height, b"cm") => number(height).map(Height::Centimetres),
+            (height, b"in") => number(height).map(Height::Inches),
+            _ => None,
+        });
+        Some(ValidPassport {
+            birth_year: number(byr?)?,
+            issue_year: number(iyr?)?,
+            expiration_year: number(eyr?)?,
+            height: height?,
+            hair_colour: text(hcl?)?,
+            eye_colour: text(ecl?)?,
+            passport_id: text(pid?)?,
+            country_id: cid.map(String::from_utf8_lossy),
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use d04::{parse, PassportSchema, Passports};
+use std::fs::File;
+use std::io::BufReader;
+
+fn passports(name: &str) -> Passports {
+    let path = format!("{}/{}", env!("CARGO_MANIFEST_DIR"), name);
+    parse(BufReader::new(File::open(path).unwrap())).unwrap()
+}
+
+#[test]
+fn explains_nothing_for_valid_passports() {
+    let schema = PassportSchema::standard();
+    let passports = passports("valid_passports.txt");
+    assert_eq!(passports.len(), 5);
+    for passport in passports.iter() {
+        let report = schema.explain(&passport);
+        assert!(report.is_valid(), "{:?}", report);
+        assert!(report.duplicate.is_empty() && report.unknown.is_empty());
+    }
+}
+
+#[test]
+fn explains_why_invalid_passports_are_invalid() {
+    let schema = PassportSchema::standard();
+    let passports = passports("invalid_passports.txt");
+    let reports: Vec<_> = passports
+        .iter()
+        .map(|passport| {
+            let report = schema.explain(&passport);
+            assert!(report.is_complete(), "{:?}", report);
+            let invalid: Vec<String> = report.invalid.iter().map(|f| f.to_string()).collect();
+            (report.lines, invalid)
+        })
+        .collect();
+
+    assert_eq!(
+        reports,
+        vec![
+            (
+                1..=2,
+                vec![
+                    String::from("eyr: 1972 not between 2020 and 2030"),
+                    String::from("hgt: 170 without unit"),
+                    String::from("pid: 186cm not 9 digits"),
+                ]
+            ),
+            (
+                4..=6,
+                vec![String::from("eyr: 1967 not between 2020 and 2030")]
+            ),
+            (8..=9, vec![String::from("hcl: dab227 not a hex colour")]),
+            (
+                11..=13,
+                vec![
+                    String::from("hgt: 59cm not between 150 and 193"),
+                    String::from("ecl: zzz not in set"),
+                    String::from("eyr: 2038 not between 2020 and 2030"),
+                    String::from("hcl: 74454a not a hex colour"),
+                    String::from("iyr: 2023 not between 2010 and 2020"),
+                    String::from("pid: 3556412378 not 9 digits"),
+                    String::from("byr: 2007 not between 1920 and 2002"),
+                ]
+            ),
+        ]
+    );
+}
@@ -0,0 +1,23 @@
+use d04::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn rejects_fields_without_a_name_or_value_separator() {
+    assert_eq!(
+        error("ecl:gry pid:860033327\n\nhcl:#fffffd byr1937\n"),
+        "invalid input: line 3, column 13: expected key:value pair, found \"byr1937\""
+    );
+    assert_eq!(
+        error("ecl:gry  :860033327\n"),
+        "invalid input: line 1, column 10: expected field name, found \":860033327\""
+    );
+}
+
+#[test]
+fn takes_empty_values_and_extra_spaces() {
+    let passports = parse(" ecl:  pid:1\n".as_bytes()).unwrap();
+    assert_eq!(passports.len(), 1);
+}
@@ -0,0 +1,88 @@
+use d04::{parse, write_json_lines, Height, PassportSchema, Passports, Validator};
+
+const VALID: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 hgt:183cm";
+
+fn passports(input: &str) -> Passports {
+    parse(input.as_bytes()).unwrap()
+}
+
+fn json_lines<I>(items: I) -> String
+where
+    I: IntoIterator,
+    I::Item: serde::Serialize,
+{
+    let mut out = Vec::new();
+    write_json_lines(&mut out, items).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn get_finds_the_first_of_repeated_keys() {
+    let passports = passports("ecl:gry pid:1\necl:blu\n");
+    let passport = passports.iter().next().unwrap();
+    assert_eq!(passport.get(b"ecl"), Some(&b"gry"[..]));
+    assert_eq!(passport.get(b"pid"), Some(&b"1"[..]));
+    assert_eq!(passport.get(b"cid"), None);
+    assert_eq!(passport.lines(), 1..=2);
+}
+
+#[test]
+fn validate_checks_the_standard_fields_too() {
+    // the custom schema only asks for a birth year, of any kind
+    let schema = PassportSchema::new().required("byr", Validator::Any);
+    let passports = passports("byr:1900 pid:12\n");
+    let passport = passports.iter().next().unwrap();
+    assert!(schema.explain(&passport).is_valid());
+
+    let report = schema.validate(&passport).unwrap_err();
+    assert_eq!(report.missing, vec!["iyr", "eyr", "hgt", "hcl", "ecl"]);
+    let invalid: Vec<String> = report.invalid.iter().map(|f| f.to_string()).collect();
+    assert_eq!(
+        invalid,
+        vec![
+            "byr: 1900 not between 1920 and 2002",
+            "pid: 12 not 9 digits"
+        ]
+    );
+    assert_eq!(report.unknown, vec![&b"pid"[..]]);
+}
+
+#[test]
+fn validate_reads_the_standard_fields() {
+    let schema = PassportSchema::standard();
+    let passports = passports(VALID);
+    let valid = schema.validate(&passports.iter().next().unwrap()).unwrap();
+    assert_eq!(valid.birth_year, 1937);
+    assert_eq!(valid.height, Height::Centimetres(183));
+    assert_eq!(valid.passport_id, "860033327");
+    assert_eq!(valid.country_id, None);
+}
+
+#[test]
+fn passports_are_written_in_input_order() {
+    let passports = passports("ecl:gry cid:7\necl:blu\n\niyr:2017\n");
+    assert_eq!(
+        json_lines(passports.iter()),
+        "{\"ecl\":\"gry\",\"cid\":\"7\",\"ecl\":\"blu\"}\n{\"iyr\":\"2017\"}\n"
+    );
+}
+
+#[test]
+fn valid_passports_are_written_with_their_keys() {
+    let schema = PassportSchema::standard();
+    let passports = passports(&format!("{}\n\n{} cid:147 hgt:70in\n", VALID, VALID));
+    let valid: Vec<_> = passports
+        .iter()
+        .map(|passport| schema.validate(&passport).unwrap())
+        .collect();
+    assert_eq!(valid[1].height, Height::Centimetres(183));
+    assert_eq!(
+        json_lines(&valid),
+        concat!(
+            "{\"byr\":1937,\"iyr\":2017,\"eyr\":2020,\"hgt\":{\"cm\":183},",
+            "\"hcl\":\"#fffffd\",\"ecl\":\"gry\",\"pid\":\"860033327\"}\n",
+            "{\"byr\":1937,\"iyr\":2017,\"eyr\":2020,\"hgt\":{\"cm\":183},",
+            "\"hcl\":\"#fffffd\",\"ecl\":\"gry\",\"pid\":\"860033327\",\"cid\":\"147\"}\n",
+        )
+    );
+}
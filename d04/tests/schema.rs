@@ -0,0 +1,110 @@
+use d04::{HeightUnit, PassportSchema, Reason, Validator};
+use std::fs::File;
+
+fn fields_json(count: usize) -> String {
+    let fields: Vec<String> = (0..count)
+        .map(|i| {
+            format!(
+                r#"{{"key": "f{}", "required": false, "validator": "any"}}"#,
+                i
+            )
+        })
+        .collect();
+    format!(r#"{{"fields": [{}]}}"#, fields.join(", "))
+}
+
+#[test]
+fn the_schema_file_is_the_standard_schema() {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/schema.json")).unwrap();
+    assert_eq!(
+        PassportSchema::from_json(file).unwrap(),
+        PassportSchema::standard()
+    );
+}
+
+#[test]
+fn rejects_unknown_schema_keys() {
+    let err = PassportSchema::from_json(r#"{"fields": [], "name": "strict"}"#.as_bytes())
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("unknown field `name`"), "{}", err);
+}
+
+#[test]
+fn takes_at_most_64_fields() {
+    let schema = PassportSchema::from_json(fields_json(64).as_bytes()).unwrap();
+    assert_eq!(schema.fields().len(), 64);
+
+    let err = PassportSchema::from_json(fields_json(65).as_bytes()).unwrap_err();
+    assert_eq!(err.to_string(), "a schema can have at most 64 fields");
+}
+
+#[test]
+#[should_panic(expected = "a schema can have at most 64 fields")]
+fn building_a_65th_field_panics() {
+    let schema = PassportSchema::from_json(fields_json(64).as_bytes()).unwrap();
+    schema.optional("f64", Validator::Any);
+}
+
+#[test]
+fn years_have_four_digits_within_the_range() {
+    let year = Validator::Year {
+        min: 1920,
+        max: 2002,
+    };
+    assert_eq!(year.check(b"1920"), Ok(()));
+    assert_eq!(year.check(b"2002"), Ok(()));
+    let out_of_range = Err(Reason::OutOfRange {
+        min: 1920,
+        max: 2002,
+    });
+    assert_eq!(year.check(b"1919"), out_of_range);
+    assert_eq!(year.check(b"2003"), out_of_range);
+    assert_eq!(year.check(b"192"), Err(Reason::NotYear));
+    assert_eq!(year.check(b"01920"), Err(Reason::NotYear));
+    assert_eq!(year.check(b"+192"), Err(Reason::NotYear));
+}
+
+#[test]
+fn heights_need_a_known_unit() {
+    let height = Validator::Height(vec![HeightUnit {
+        unit: String::from("cm"),
+        min: 150,
+        max: 193,
+    }]);
+    assert_eq!(height.check(b"150cm"), Ok(()));
+    assert_eq!(height.check(b"193cm"), Ok(()));
+    assert_eq!(
+        height.check(b"194cm"),
+        Err(Reason::OutOfRange { min: 150, max: 193 })
+    );
+    assert_eq!(height.check(b"170"), Err(Reason::WithoutUnit));
+    assert_eq!(height.check(b"70in"), Err(Reason::UnknownUnit));
+    assert_eq!(height.check(b"cm"), Err(Reason::NotNumber));
+    assert_eq!(height.check(b""), Err(Reason::NotNumber));
+}
+
+#[test]
+fn hex_colours_are_lowercase() {
+    assert_eq!(Validator::HexColour.check(b"#a97842"), Ok(()));
+    assert_eq!(
+        Validator::HexColour.check(b"#A97842"),
+        Err(Reason::NotHexColour)
+    );
+    assert_eq!(
+        Validator::HexColour.check(b"#a9784"),
+        Err(Reason::NotHexColour)
+    );
+    assert_eq!(
+        Validator::HexColour.check(b"a978420"),
+        Err(Reason::NotHexColour)
+    );
+}
+
+#[test]
+fn digits_do_not_take_a_sign() {
+    let digits = Validator::Digits(9);
+    assert_eq!(digits.check(b"000000001"), Ok(()));
+    assert_eq!(digits.check(b"+00000001"), Err(Reason::NotDigits(9)));
+    assert_eq!(digits.check(b"0123456789"), Err(Reason::NotDigits(9)));
+}
@@ -0,0 +1,205 @@
+use std::fmt;
+
+/// The size of a plane, given as how many letters of a boarding pass pick
+/// the row and how many pick the column. Each letter halves what is left, so
+/// a plane has `2^row_bits` rows of `2^column_bits` seats.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Plane {
+    row_bits: u32,
+    column_bits: u32,
+}
+
+impl Plane {
+    /// The plane of the puzzle: 128 rows of 8 seats.
+    pub const STANDARD: Plane = Plane {
+        row_bits: 7,
+        column_bits: 3,
+    };
+
+    /// Panics if the plane has more than `2^32` seats, since seat ids are
+    /// `u32`.
+    pub fn new(row_bits: u32, column_bits: u32) -> Self {
+        assert!(
+            row_bits + column_bits <= 32,
+            "a plane can have at most 32 bits of seats, not {}",
+            row_bits + column_bits
+        );
+        Plane {
+            row_bits,
+            column_bits,
+        }
+    }
+
+    pub fn row_bits(&self) -> u32 {
+        self.row_bits
+    }
+
+    pub fn column_bits(&self) -> u32 {
+        self.column_bits
+    }
+
+    pub fn rows(&self) -> u64 {
+        1 << self.row_bits
+    }
+
+    pub fn columns(&self) -> u64 {
+        1 << self.column_bits
+    }
+
+    pub fn seats(&self) -> u64 {
+        1 << (self.row_bits + self.column_bits)
+    }
+
+    /// How many letters a boarding pass for this plane has.
+    pub fn pass_len(&self) -> usize {
+        (self.row_bits + self.column_bits) as usize
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Plane::STANDARD
+    }
+}
+
+/// Why a boarding pass could not be decoded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The pass has `found` letters instead of `expected`.
+    Length { expected: usize, found: usize },
+    /// The letter at `position`, counted from 0, is not one of the two the
+    /// pass can have there.
+    Letter {
+        position: usize,
+        found: u8,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Length { expected, found } => {
+                write!(f, "expected {} letters, found {}", expected, found)
+            }
+            DecodeError::Letter {
+                position,
+                found,
+                expected,
+            } => write!(
+                f,
+                "expected {} at position {}, found {:?}",
+                expected,
+                position + 1,
+                *found as char
+            ),
+        }
+    }
+}
+
+/// A seat on a plane. As text it is written the way boarding passes are:
+/// `F` and `B` pick the front or back half of the rows, then `L` and `R` the
+/// left or right half of the seats in the row.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct BoardingPass {
+    row: u32,
+    column: u32,
+    plane: Plane,
+}
+
+// reads letters as the bits of a number, the first letter being the highest
+fn bits(
+    letters: &[u8],
+    offset: usize,
+    zero: u8,
+    one: u8,
+    expected: &'static str,
+) -> Result<u32, DecodeError> {
+    letters
+        .iter()
+        .enumerate()
+        .try_fold(0, |number, (i, c)| match *c {
+            c if c == zero => Ok(number << 1),
+            c if c == one => Ok(number << 1 | 1),
+            found => Err(DecodeError::Letter {
+                position: offset + i,
+                found,
+                expected,
+            }),
+        })
+}
+
+impl BoardingPass {
+    /// The seat in `row` and `column`, or `None` if the plane has no such
+    /// seat.
+    pub fn new(row: u32, column: u32, plane: Plane) -> Option<Self> {
+        if (row as u64) < plane.rows() && (column as u64) < plane.columns() {
+            Some(BoardingPass { row, column, plane })
+        } else {
+            None
+        }
+    }
+
+    /// The seat with the id, or `None` if the plane has no such seat.
+    pub fn from_id(id: u32, plane: Plane) -> Option<Self> {
+        if (id as u64) < plane.seats() {
+            let column_mask = (plane.columns() - 1) as u32;
+            Some(BoardingPass {
+                row: ((id as u64) >> plane.column_bits) as u32,
+                column: id & column_mask,
+                plane,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn decode(pass: &[u8], plane: Plane) -> Result<Self, DecodeError> {
+        if pass.len() != plane.pass_len() {
+            return Err(DecodeError::Length {
+                expected: plane.pass_len(),
+                found: pass.len(),
+            });
+        }
+        let (row, column) = pass.split_at(plane.row_bits as usize);
+        Ok(BoardingPass {
+            row: bits(row, 0, b'F', b'B', "'F' or 'B'")?,
+            column: bits(column, row.len(), b'L', b'R', "'L' or 'R'")?,
+            plane,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        let letters = |number: u32, bits: u32, zero: char, one: char| {
+            (0..bits)
+                .rev()
+                .map(move |bit| if number >> bit & 1 == 1 { one } else { zero })
+        };
+        letters(self.row, self.plane.row_bits, 'F', 'B')
+            .chain(letters(self.column, self.plane.column_bits, 'L', 'R'))
+            .collect()
+    }
+
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    pub fn plane(&self) -> Plane {
+        self.plane
+    }
+
+    /// The seat id, which numbers the seats row by row from the front.
+    pub fn id(&self) -> u32 {
+        ((self.row as u64) << self.plane.column_bits | self.column as u64) as u32
+    }
+}
+
+impl fmt::Display for BoardingPass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
@@ -1,45 +1,49 @@
+use crate::boarding_pass::{BoardingPass, Plane};
+
+/// The boarding passes of one plane, in input order, with the lines they
+/// were read from.
 pub struct FlightPassCollection {
-    ids: Vec<u16>,
+    plane: Plane,
+    passes: Vec<BoardingPass>,
+    lines: Vec<usize>,
 }
 
 impl FlightPassCollection {
-    pub fn new(buffer: Vec<u16>) -> Self {
-        FlightPassCollection { ids: buffer }
+    pub fn new(plane: Plane) -> Self {
+        FlightPassCollection {
+            plane,
+            passes: Vec::new(),
+            lines: Vec::new(),
+        }
     }
 
-    pub fn iter(&self) -> FlightPassCollectionIterator {
-        FlightPassCollectionIterator {
-            collection: &self,
-            i: 0,
-        }
+    pub fn push(&mut self, line: usize, pass: BoardingPass) {
+        self.passes.push(pass);
+        self.lines.push(line);
     }
-}
 
-pub struct FlightPassCollectionIterator<'a> {
-    collection: &'a FlightPassCollection,
-    i: usize,
-}
+    pub fn plane(&self) -> Plane {
+        self.plane
+    }
 
-impl<'a> Iterator for FlightPassCollectionIterator<'a> {
-    type Item = FlightPass<'a>;
-    fn next(&mut self) -> Option<FlightPass<'a>> {
-        let id: &'a u16;
-        if self.i == self.collection.ids.len() {
-            return None;
-        }
-        id = &self.collection.ids[self.i];
-        self.i += 1;
-        Some(FlightPass { id })
+    pub fn len(&self) -> usize {
+        self.passes.len()
     }
-}
 
-#[derive(Debug)]
-pub struct FlightPass<'a> {
-    pub id: &'a u16,
-}
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, BoardingPass> {
+        self.passes.iter()
+    }
+
+    /// The passes with the input lines they were on.
+    pub fn with_lines(&self) -> impl Iterator<Item = (usize, &BoardingPass)> + '_ {
+        self.lines.iter().copied().zip(&self.passes)
+    }
 
-impl<'a> FlightPass<'a> {
-    pub fn get_id(&self) -> u16 {
-        *self.id
+    pub fn ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.passes.iter().map(BoardingPass::id)
     }
 }
@@ -1,59 +1,103 @@
+mod boarding_pass;
 mod flight_pass;
-use flight_pass::FlightPassCollection;
-use std::cmp;
+mod seat_map;
+
+pub use boarding_pass::{BoardingPass, DecodeError, Plane};
+use common::{LineReader, ParseError, Solution};
+pub use flight_pass::FlightPassCollection;
+pub use seat_map::{Duplicate, EmptyRun, SeatMap};
 use std::io::BufRead;
 
-pub fn parse<R>(mut reader: R) -> FlightPassCollection
+/// Reads boarding passes for the plane of the puzzle.
+pub fn parse<R>(reader: R) -> Result<FlightPassCollection, ParseError>
+where
+    R: BufRead,
+{
+    parse_plane(reader, Plane::STANDARD)
+}
+
+/// Reads boarding passes for a plane of any size, stopping at the first line
+/// that is not one.
+pub fn parse_plane<R>(reader: R, plane: Plane) -> Result<FlightPassCollection, ParseError>
 where
     R: BufRead,
 {
-    let mut ids: Vec<u16> = Vec::new();
-    let mut buf: Vec<u8> = Vec::with_capacity(11);
+    let (passes, malformed) = parse_lenient(reader, plane)?;
+    match malformed.first() {
+        Some(malformed) => Err(malformed.to_parse_error()),
+        None => Ok(passes),
+    }
+}
 
-    while let Ok(n) = reader.read_until(b'\n', &mut buf) {
-        match n {
-            0 => break,
-            10 | 11 => {
-                let mut s: u16 = 0;
-                let mut width = 128;
-                for c in &buf[0..7] {
-                    width /= 2;
-                    if c == &b'B' {
-                        s += width;
-                    }
-                }
-                let row = s;
-                s = 0;
-                width = 8;
-                for c in &buf[7..10] {
-                    width /= 2;
-                    if c == &b'R' {
-                        s += width;
-                    }
-                }
-                ids.push((row * 8) + s);
+/// A line that is not a boarding pass for the plane.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Malformed {
+    pub line: usize,
+    pub text: Vec<u8>,
+    pub error: DecodeError,
+}
+
+impl Malformed {
+    pub fn to_parse_error(&self) -> ParseError {
+        match self.error {
+            DecodeError::Length { expected, found } if found < expected => {
+                ParseError::syntax(self.line, found + 1, b"", "seat character")
             }
-            _ => (),
+            DecodeError::Length { expected, .. } => ParseError::syntax(
+                self.line,
+                expected + 1,
+                &self.text[expected..],
+                "end of line",
+            ),
+            DecodeError::Letter {
+                position,
+                found,
+                expected,
+            } => ParseError::syntax(self.line, position + 1, &[found], expected),
         }
-        buf.clear();
     }
-
-    FlightPassCollection::new(ids)
 }
 
-pub fn p1_solve(flight_passes: &FlightPassCollection) -> u16 {
-    let mut max_id = 0;
-    for flight_pass in flight_passes.iter() {
-        max_id = cmp::max(max_id, flight_pass.get_id());
+/// Reads boarding passes for a plane of any size, setting the lines that are
+/// not boarding passes aside instead of stopping at them. Only reading the
+/// input can fail.
+pub fn parse_lenient<R>(
+    reader: R,
+    plane: Plane,
+) -> Result<(FlightPassCollection, Vec<Malformed>), ParseError>
+where
+    R: BufRead,
+{
+    let mut passes = FlightPassCollection::new(plane);
+    let mut malformed = Vec::new();
+    let mut lines = LineReader::new(reader);
+
+    while let Some((line, text)) = lines.next_line()? {
+        if text.is_empty() {
+            continue;
+        }
+        match BoardingPass::decode(text, plane) {
+            Ok(pass) => passes.push(line, pass),
+            Err(error) => malformed.push(Malformed {
+                line,
+                text: text.to_vec(),
+                error,
+            }),
+        }
     }
-    max_id
+
+    Ok((passes, malformed))
 }
 
-pub fn p2_solve(flight_passes: &FlightPassCollection) -> Option<u16> {
-    let mut map: [u64; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-    for flight_pass in flight_passes.iter() {
-        let id = flight_pass.get_id();
-        map[(id as usize) / 64] |= 1 << (id % 64);
+pub fn p1_solve(flight_passes: &FlightPassCollection) -> Option<u32> {
+    flight_passes.ids().max()
+}
+
+pub fn p2_solve(flight_passes: &FlightPassCollection) -> Option<u32> {
+    let seats = flight_passes.plane().seats() as usize;
+    let mut map = vec![0u64; seats.div_ceil(64)];
+    for id in flight_passes.ids() {
+        map[id as usize / 64] |= 1 << (id % 64);
     }
     let mut i = 0;
     let mut iter = map.iter();
@@ -61,21 +105,40 @@ pub fn p2_solve(flight_passes: &FlightPassCollection) -> Option<u16> {
         if bucket == &0 {
             i += 1;
         } else {
-            let masked = bucket | (u64::max_value() >> (63 - bucket.trailing_zeros()));
-            if masked == u64::max_value() {
+            let masked = bucket | (u64::MAX >> (63 - bucket.trailing_zeros()));
+            if masked == u64::MAX {
                 i += 1;
                 break;
             } else {
-                return Some((i * 64 + bucket.trailing_zeros()) as u16);
+                return Some(i * 64 + bucket.trailing_zeros());
             }
         }
     }
     for bucket in &mut iter {
-        if bucket == &u64::max_value() {
+        if bucket == &u64::MAX {
             i += 1;
         } else {
-            return Some((i * 64 + bucket.trailing_ones()) as u16);
+            return Some(i * 64 + bucket.trailing_ones());
         }
     }
     None
 }
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = FlightPassCollection;
+    type Output = u32;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
@@ -1,23 +1,140 @@
 extern crate d05;
-use d05::{p1_solve, p2_solve, parse};
-use std::{env, io};
+use d05::{p1_solve, p2_solve, parse_lenient, Duplicate, Plane, SeatMap};
+use std::{env, io, process};
+
+const USAGE: &str = "\
+usage: d05 <part> < input.txt
+       d05 audit < input.txt";
+
+enum Command {
+    Part(u8),
+    Audit,
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let command = match args.first().map(|arg| arg.trim()) {
+        Some("audit") => Command::Audit,
+        Some(part) => Command::Part(
+            part.parse()
+                .map_err(|_| format!("unknown command '{}'", part))?,
+        ),
+        None => return Err(String::from("missing part or command")),
+    };
+    match args.get(1) {
+        Some(arg) => Err(format!("unexpected argument '{}'", arg)),
+        None => Ok(command),
+    }
+}
+
+fn lines(duplicate: &Duplicate) -> String {
+    let lines: Vec<String> = duplicate
+        .lines
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+    lines.join(", ")
+}
+
+fn audit(map: &SeatMap) {
+    let plane = map.plane();
+    println!(
+        "{} of {} seats taken",
+        map.taken_count(),
+        plane.rows() * plane.columns()
+    );
+
+    let full: Vec<String> = map.full_rows().map(|row| row.to_string()).collect();
+    if full.is_empty() {
+        println!("full rows: none");
+    } else {
+        println!("full rows: {}", full.join(", "));
+    }
+
+    println!("empty seats:");
+    for run in map.empty_runs() {
+        let last = run.columns.end - 1;
+        if run.columns.start == last {
+            println!("  row {}, seat {}", run.row, last);
+        } else {
+            println!(
+                "  row {}, seats {}-{} ({} seats)",
+                run.row,
+                run.columns.start,
+                last,
+                run.len()
+            );
+        }
+    }
+
+    if !map.duplicates().is_empty() {
+        println!("seats with more than one pass:");
+        for duplicate in map.duplicates() {
+            println!(
+                "  {} (row {}, seat {}, id {}) on lines {}",
+                duplicate.seat,
+                duplicate.seat.row(),
+                duplicate.seat.column(),
+                duplicate.seat.id(),
+                lines(duplicate)
+            );
+        }
+    }
+
+    print!("\n{}", map);
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    let part = args
-        .get(1)
-        .expect("Missing argument 'part'")
-        .trim()
-        .parse()
-        .expect("Part must be a number");
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
 
-    let flight_passes = parse(io::stdin().lock());
+    let (flight_passes, malformed) = match parse_lenient(io::stdin().lock(), Plane::STANDARD) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+    if !malformed.is_empty() {
+        for line in &malformed {
+            eprintln!("{}", line.to_parse_error());
+        }
+        eprintln!("{} lines are not boarding passes", malformed.len());
+        process::exit(1);
+    }
 
-    let result = match part {
-        1 => Some(p1_solve(&flight_passes)),
-        2 => p2_solve(&flight_passes),
-        _ => None,
+    let map = SeatMap::new(&flight_passes);
+    let result = match command {
+        Command::Part(part) => {
+            for duplicate in map.duplicates() {
+                eprintln!(
+                    "warning: seat {} is on lines {}",
+                    duplicate.seat.id(),
+                    lines(duplicate)
+                );
+            }
+            if !map.duplicates().is_empty() {
+                eprintln!(
+                    "warning: {} seats have more than one boarding pass",
+                    map.duplicates().len()
+                );
+            }
+            match part {
+                1 => p1_solve(&flight_passes),
+                2 => p2_solve(&flight_passes),
+                _ => None,
+            }
+        }
+        Command::Audit => {
+            audit(&map);
+            None
+        }
     };
 
     if let Some(result) = result {
@@ -0,0 +1,164 @@
+use crate::boarding_pass::{BoardingPass, Plane};
+use crate::flight_pass::FlightPassCollection;
+use common::BitGrid;
+use std::fmt;
+use std::ops::Range;
+
+/// Seats next to each other in a row that nobody has a boarding pass for.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmptyRun {
+    pub row: u32,
+    pub columns: Range<u32>,
+}
+
+impl EmptyRun {
+    pub fn len(&self) -> u32 {
+        self.columns.end - self.columns.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+}
+
+/// A seat that more than one boarding pass is for, with the input lines of
+/// those passes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Duplicate {
+    pub seat: BoardingPass,
+    pub lines: Vec<usize>,
+}
+
+/// Which seats of a plane are taken, going by a collection of boarding
+/// passes.
+///
+/// As text, every row is a line, with `#` for a seat that is taken, `!` for
+/// one that more than one pass is for, and `.` for an empty seat.
+pub struct SeatMap {
+    plane: Plane,
+    taken: BitGrid,
+    duplicates: Vec<Duplicate>,
+}
+
+impl SeatMap {
+    /// Panics if the plane has more seats than fit in memory.
+    pub fn new(passes: &FlightPassCollection) -> Self {
+        let plane = passes.plane();
+        let mut taken = BitGrid::new(plane.columns() as usize, plane.rows() as usize);
+        let mut duplicates: Vec<Duplicate> = Vec::new();
+        for (line, pass) in passes.with_lines() {
+            let (x, y) = (pass.column() as usize, pass.row() as usize);
+            if taken.get(x, y) == Some(true) {
+                match duplicates.iter_mut().find(|dup| dup.seat == *pass) {
+                    Some(duplicate) => duplicate.lines.push(line),
+                    None => {
+                        // the first pass for the seat is only known to be one
+                        // of several now
+                        let first = passes
+                            .with_lines()
+                            .find(|(_, other)| *other == pass)
+                            .map(|(line, _)| line)
+                            .unwrap_or(line);
+                        duplicates.push(Duplicate {
+                            seat: *pass,
+                            lines: vec![first, line],
+                        })
+                    }
+                }
+            } else {
+                taken.set(x, y, true);
+            }
+        }
+        duplicates.sort_by_key(|duplicate| duplicate.seat.id());
+        SeatMap {
+            plane,
+            taken,
+            duplicates,
+        }
+    }
+
+    pub fn plane(&self) -> Plane {
+        self.plane
+    }
+
+    pub fn is_taken(&self, seat: &BoardingPass) -> bool {
+        self.taken.get(seat.column() as usize, seat.row() as usize) == Some(true)
+    }
+
+    /// How many seats have at least one boarding pass.
+    pub fn taken_count(&self) -> usize {
+        self.taken.rows().map(|row| row.count_ones()).sum()
+    }
+
+    /// The seats nobody has a pass for, front to back.
+    pub fn empty_seats(&self) -> impl Iterator<Item = BoardingPass> + '_ {
+        let plane = self.plane;
+        self.taken.rows().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, taken)| !taken)
+                .filter_map(move |(x, _)| BoardingPass::new(y as u32, x as u32, plane))
+        })
+    }
+
+    /// Every stretch of empty seats, row by row, left to right.
+    pub fn empty_runs(&self) -> impl Iterator<Item = EmptyRun> + '_ {
+        self.taken.rows().enumerate().flat_map(|(y, row)| {
+            let mut runs = Vec::new();
+            let mut start = None;
+            for (x, taken) in row.iter().chain(Some(true)).enumerate() {
+                match (taken, start) {
+                    (false, None) => start = Some(x as u32),
+                    (true, Some(first)) => {
+                        runs.push(EmptyRun {
+                            row: y as u32,
+                            columns: first..x as u32,
+                        });
+                        start = None;
+                    }
+                    _ => (),
+                }
+            }
+            runs
+        })
+    }
+
+    /// The rows where every seat is taken.
+    pub fn full_rows(&self) -> impl Iterator<Item = u32> + '_ {
+        self.taken
+            .rows()
+            .enumerate()
+            .filter(|(_, row)| row.count_ones() == row.len())
+            .map(|(y, _)| y as u32)
+    }
+
+    /// The seats more than one pass is for, by seat id.
+    pub fn duplicates(&self) -> &[Duplicate] {
+        &self.duplicates
+    }
+}
+
+impl fmt::Display for SeatMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = (self.plane.rows() - 1).to_string().len();
+        let mut duplicates = self.duplicates.iter().peekable();
+        let mut line = String::with_capacity(label + 2 + self.taken.width());
+        for (y, row) in self.taken.rows().enumerate() {
+            line.clear();
+            line.push_str(&format!("{:>width$} ", y, width = label));
+            for (x, taken) in row.iter().enumerate() {
+                let duplicate = duplicates
+                    .next_if(|dup| dup.seat.row() as usize == y && dup.seat.column() as usize == x)
+                    .is_some();
+                line.push(match (taken, duplicate) {
+                    (_, true) => '!',
+                    (true, false) => '#',
+                    (false, false) => '.',
+                });
+            }
+            line.push('\n');
+            f.write_str(&line)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use d05::{parse_plane, BoardingPass, DecodeError, Plane};
+
+#[test]
+fn decodes_the_puzzle_examples() {
+    for (pass, row, column, id) in &[
+        ("FBFBBFFRLR", 44, 5, 357),
+        ("BFFFBBFRRR", 70, 7, 567),
+        ("FFFBBBFRRR", 14, 7, 119),
+        ("BBFFBBFRLL", 102, 4, 820),
+    ] {
+        let decoded = BoardingPass::decode(pass.as_bytes(), Plane::STANDARD).unwrap();
+        assert_eq!(
+            (decoded.row(), decoded.column(), decoded.id()),
+            (*row, *column, *id)
+        );
+        assert_eq!(decoded.encode(), *pass);
+    }
+}
+
+#[test]
+fn every_standard_seat_round_trips() {
+    for id in 0..1024 {
+        let pass = BoardingPass::from_id(id, Plane::STANDARD).unwrap();
+        let decoded = BoardingPass::decode(pass.encode().as_bytes(), Plane::STANDARD).unwrap();
+        assert_eq!(decoded, pass);
+        assert_eq!(decoded.id(), id);
+        assert_eq!(
+            BoardingPass::new(pass.row(), pass.column(), Plane::STANDARD),
+            Some(pass)
+        );
+    }
+    assert_eq!(BoardingPass::from_id(1024, Plane::STANDARD), None);
+}
+
+#[test]
+fn larger_planes_round_trip() {
+    let plane = Plane::new(10, 4);
+    assert_eq!(
+        (plane.rows(), plane.columns(), plane.pass_len()),
+        (1024, 16, 14)
+    );
+    for id in 0..plane.seats() as u32 {
+        let pass = BoardingPass::from_id(id, plane).unwrap();
+        let code = pass.encode();
+        assert_eq!(code.len(), 14);
+        assert_eq!(BoardingPass::decode(code.as_bytes(), plane), Ok(pass));
+    }
+
+    let last = BoardingPass::decode(b"BBBBBBBBBBRRRR", plane).unwrap();
+    assert_eq!((last.row(), last.column(), last.id()), (1023, 15, 16383));
+    assert_eq!(BoardingPass::new(1024, 0, plane), None);
+    assert_eq!(BoardingPass::new(0, 16, plane), None);
+}
+
+#[test]
+fn planes_can_have_a_single_row_or_column() {
+    let aisle = Plane::new(0, 3);
+    let pass = BoardingPass::decode(b"RLR", aisle).unwrap();
+    assert_eq!((pass.row(), pass.column(), pass.id()), (0, 5, 5));
+
+    let column = Plane::new(3, 0);
+    let pass = BoardingPass::decode(b"BFB", column).unwrap();
+    assert_eq!((pass.row(), pass.column(), pass.id()), (5, 0, 5));
+    assert_eq!(pass.encode(), "BFB");
+}
+
+#[test]
+fn decode_rejects_other_lengths_and_letters() {
+    assert_eq!(
+        BoardingPass::decode(b"FBFBBFFRL", Plane::STANDARD),
+        Err(DecodeError::Length {
+            expected: 10,
+            found: 9
+        })
+    );
+    assert_eq!(
+        BoardingPass::decode(b"FBFBBFLRLR", Plane::STANDARD),
+        Err(DecodeError::Letter {
+            position: 6,
+            found: b'L',
+            expected: "'F' or 'B'"
+        })
+    );
+    assert_eq!(
+        BoardingPass::decode(b"FBFBBFFRBR", Plane::STANDARD),
+        Err(DecodeError::Letter {
+            position: 8,
+            found: b'B',
+            expected: "'L' or 'R'"
+        })
+    );
+}
+
+#[test]
+fn parse_reads_passes_for_the_given_plane() {
+    let passes = parse_plane(
+        "BBBBBBBBBBRRRR\nFFFFFFFFFFLLLR\n".as_bytes(),
+        Plane::new(10, 4),
+    )
+    .unwrap();
+    assert_eq!(passes.ids().collect::<Vec<_>>(), vec![16383, 1]);
+    assert!(parse_plane("FBFBBFFRLR\n".as_bytes(), Plane::new(10, 4)).is_err());
+}
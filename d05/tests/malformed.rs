@@ -0,0 +1,46 @@
+use d05::{parse, parse_lenient, DecodeError, Plane, SeatMap};
+
+#[test]
+fn parse_rejects_letters_outside_the_alphabet() {
+    let err = parse("FBFBBFFRLR\nXXXXXXXQQQ\n".as_bytes()).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "invalid input: line 2, column 1: expected 'F' or 'B', found \"X\""
+    );
+    let err = parse("FBFBBFFRLQ\n".as_bytes()).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "invalid input: line 1, column 10: expected 'L' or 'R', found \"Q\""
+    );
+}
+
+#[test]
+fn lenient_parse_reports_every_malformed_line() {
+    let input = "FBFBBFFRLR\nXXXXXXXQQQ\n\nFBF\nBBFFBBFRLL\nFBFBBFFRLRR\n";
+    let (passes, malformed) = parse_lenient(input.as_bytes(), Plane::STANDARD).unwrap();
+    assert_eq!(passes.ids().collect::<Vec<_>>(), vec![357, 820]);
+
+    let lines: Vec<usize> = malformed.iter().map(|line| line.line).collect();
+    assert_eq!(lines, vec![2, 4, 6]);
+    assert_eq!(
+        malformed[1].error,
+        DecodeError::Length {
+            expected: 10,
+            found: 3
+        }
+    );
+    assert_eq!(malformed[2].text, b"FBFBBFFRLRR");
+}
+
+#[test]
+fn duplicate_seats_are_found_with_their_lines() {
+    let input = "FBFBBFFRLR\nBBFFBBFRLL\nFBFBBFFRLR\nFFFBBBFRRR\nFBFBBFFRLR\nBBFFBBFRLL\n";
+    let passes = parse(input.as_bytes()).unwrap();
+    let map = SeatMap::new(&passes);
+    let duplicates: Vec<(u32, Vec<usize>)> = map
+        .duplicates()
+        .iter()
+        .map(|duplicate| (duplicate.seat.id(), duplicate.lines.clone()))
+        .collect();
+    assert_eq!(duplicates, vec![(357, vec![1, 3, 5]), (820, vec![2, 6])]);
+}
@@ -0,0 +1,68 @@
+use d05::{BoardingPass, EmptyRun, FlightPassCollection, Plane, SeatMap};
+
+// four rows of four seats: the front row full, one seat of row 1 on two
+// passes, one seat of row 2 taken and the back row empty
+fn seat_map() -> SeatMap {
+    let plane = Plane::new(2, 2);
+    let mut passes = FlightPassCollection::new(plane);
+    let seats = [(0, 0), (0, 1), (0, 2), (0, 3), (1, 1), (1, 1), (2, 3)];
+    for (i, (row, column)) in seats.iter().enumerate() {
+        passes.push(i + 1, BoardingPass::new(*row, *column, plane).unwrap());
+    }
+    SeatMap::new(&passes)
+}
+
+#[test]
+fn counts_taken_seats_once() {
+    let map = seat_map();
+    assert_eq!(map.taken_count(), 6);
+    assert!(map.is_taken(&BoardingPass::new(1, 1, map.plane()).unwrap()));
+    assert!(!map.is_taken(&BoardingPass::new(3, 0, map.plane()).unwrap()));
+}
+
+#[test]
+fn lists_empty_seats_front_to_back() {
+    let seats: Vec<(u32, u32)> = seat_map()
+        .empty_seats()
+        .map(|seat| (seat.row(), seat.column()))
+        .collect();
+    assert_eq!(
+        seats,
+        vec![
+            (1, 0),
+            (1, 2),
+            (1, 3),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (3, 0),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+        ]
+    );
+}
+
+#[test]
+fn finds_runs_of_empty_seats_and_full_rows() {
+    let map = seat_map();
+    let run = |row, columns| EmptyRun { row, columns };
+    let runs: Vec<EmptyRun> = map.empty_runs().collect();
+    assert_eq!(
+        runs,
+        vec![run(1, 0..1), run(1, 2..4), run(2, 0..3), run(3, 0..4)]
+    );
+    assert_eq!(
+        runs.iter().map(EmptyRun::len).collect::<Vec<_>>(),
+        vec![1, 2, 3, 4]
+    );
+    assert_eq!(map.full_rows().collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+fn draws_the_plane_with_duplicates_marked() {
+    let map = seat_map();
+    assert_eq!(map.duplicates().len(), 1);
+    assert_eq!(map.duplicates()[0].lines, vec![5, 6]);
+    assert_eq!(map.to_string(), "0 ####\n1 .!..\n2 ...#\n3 ....\n");
+}
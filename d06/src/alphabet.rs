@@ -0,0 +1,122 @@
+use crate::declaration::Answers;
+use std::fmt;
+
+// marks a byte that is not an answer
+const NONE: u8 = u8::MAX;
+
+/// Why a list of symbols cannot be an alphabet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AlphabetError {
+    /// There are more symbols than `Answers` has questions.
+    TooMany(usize),
+    /// The symbol is in the list more than once.
+    Repeated(u8),
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlphabetError::TooMany(len) => write!(
+                f,
+                "an alphabet can have at most {} symbols, not {}",
+                Answers::QUESTIONS,
+                len
+            ),
+            AlphabetError::Repeated(symbol) => {
+                write!(f, "symbol {:?} is repeated", *symbol as char)
+            }
+        }
+    }
+}
+
+/// The bytes answers are written with, and which question each stands for.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Alphabet {
+    questions: [u8; 256],
+    // the symbol each question is written with
+    symbols: Vec<u8>,
+    expected: &'static str,
+}
+
+impl Alphabet {
+    /// The questions `a` to `z`.
+    pub fn lowercase() -> Self {
+        let mut alphabet = Alphabet::new((b'a'..=b'z').collect(), "answer a-z");
+        for (question, symbol) in (b'a'..=b'z').enumerate() {
+            alphabet.questions[symbol as usize] = question as u8;
+        }
+        alphabet
+    }
+
+    /// The questions `a` to `z`, where `A` is the same question as `a`.
+    pub fn case_insensitive() -> Self {
+        let mut alphabet = Alphabet::lowercase();
+        alphabet.expected = "answer a-z or A-Z";
+        for (question, symbol) in (b'A'..=b'Z').enumerate() {
+            alphabet.questions[symbol as usize] = question as u8;
+        }
+        alphabet
+    }
+
+    /// A question for each of the symbols, in the order given.
+    pub fn symbols(symbols: &[u8]) -> Result<Self, AlphabetError> {
+        if symbols.len() > Answers::QUESTIONS {
+            return Err(AlphabetError::TooMany(symbols.len()));
+        }
+        let mut alphabet = Alphabet::new(symbols.to_vec(), "answer symbol");
+        for (question, symbol) in symbols.iter().enumerate() {
+            let slot = &mut alphabet.questions[*symbol as usize];
+            if *slot != NONE {
+                return Err(AlphabetError::Repeated(*symbol));
+            }
+            *slot = question as u8;
+        }
+        Ok(alphabet)
+    }
+
+    fn new(symbols: Vec<u8>, expected: &'static str) -> Self {
+        Alphabet {
+            questions: [NONE; 256],
+            symbols,
+            expected,
+        }
+    }
+
+    /// The question the byte stands for, if it stands for one.
+    #[inline]
+    pub fn question(&self, byte: u8) -> Option<usize> {
+        match self.questions[byte as usize] {
+            NONE => None,
+            question => Some(question as usize),
+        }
+    }
+
+    /// How many questions there are.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// What a byte outside the alphabet is reported as not being.
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Writes the answers with the symbols of their questions.
+    pub fn spell(&self, answers: Answers) -> String {
+        answers
+            .iter()
+            .filter_map(|question| self.symbols.get(question))
+            .map(|symbol| *symbol as char)
+            .collect()
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::lowercase()
+    }
+}
@@ -1,5 +1,72 @@
+use std::ops::{BitAnd, BitOr, Sub};
+
+/// The questions someone answered "yes" to, as a set of question numbers
+/// counted from 0. Which symbol stands for which question is up to the
+/// `Alphabet` the answers were read with.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Answers(u128);
+
+impl Answers {
+    /// The most questions a form can have.
+    pub const QUESTIONS: usize = 128;
+
+    /// Every question there can be.
+    pub fn all() -> Self {
+        Answers(u128::MAX)
+    }
+
+    /// Panics if there is no such question.
+    pub fn insert(&mut self, question: usize) {
+        assert!(question < Answers::QUESTIONS, "no question {}", question);
+        self.0 |= 1 << question;
+    }
+
+    pub fn contains(&self, question: usize) -> bool {
+        question < Answers::QUESTIONS && self.0 >> question & 1 == 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The questions, lowest first.
+    pub fn iter(&self) -> impl Iterator<Item = usize> {
+        let answers = *self;
+        (0..Answers::QUESTIONS).filter(move |question| answers.contains(*question))
+    }
+}
+
+impl BitOr for Answers {
+    type Output = Answers;
+
+    fn bitor(self, other: Answers) -> Answers {
+        Answers(self.0 | other.0)
+    }
+}
+
+impl BitAnd for Answers {
+    type Output = Answers;
+
+    fn bitand(self, other: Answers) -> Answers {
+        Answers(self.0 & other.0)
+    }
+}
+
+/// The questions of the first set that are not in the second.
+impl Sub for Answers {
+    type Output = Answers;
+
+    fn sub(self, other: Answers) -> Answers {
+        Answers(self.0 & !other.0)
+    }
+}
+
 #[derive(Debug)]
 pub struct Declaration {
     pub group: u16,
-    pub answers: u32,
+    pub answers: Answers,
 }
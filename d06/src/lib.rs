@@ -1,75 +1,76 @@
+mod alphabet;
 mod declaration;
-use declaration::Declaration;
+mod query;
+pub use alphabet::{Alphabet, AlphabetError};
+use common::{LineReader, ParseError, Solution};
+pub use declaration::{Answers, Declaration};
+pub use query::{groups, query, Group, Query};
 use std::io::BufRead;
 
-pub fn parse<R>(mut reader: R) -> Vec<Declaration>
+/// Reads declarations answered with the letters `a` to `z`.
+pub fn parse<R>(reader: R) -> Result<Vec<Declaration>, ParseError>
+where
+    R: BufRead,
+{
+    parse_with(reader, &Alphabet::lowercase())
+}
+
+/// Reads declarations answered with the symbols of the alphabet. Groups are
+/// separated by blank lines, which may end in `\r\n`.
+pub fn parse_with<R>(reader: R, alphabet: &Alphabet) -> Result<Vec<Declaration>, ParseError>
 where
     R: BufRead,
 {
     let mut result: Vec<Declaration> = Vec::new();
-    let mut in_buf: [u8; 4096] = [0; 4096];
+    let mut lines = LineReader::new(reader);
     let mut group = 0;
-    let mut answers = 0;
-    while let Ok(in_len) = reader.read(&mut in_buf) {
-        if in_len == 0 {
-            break;
+    while let Some((line, declaration)) = lines.next_line()? {
+        if declaration.is_empty() {
+            group += 1;
+            continue;
         }
-        if (result.capacity() - result.len()) < (in_len / 8) {
-            result.reserve(in_len / 8);
-        }
-        for c in in_buf[..in_len].iter() {
-            if c == &b'\n' {
-                if answers == 0 {
-                    group += 1;
-                } else {
-                    result.push(Declaration { group, answers });
-                    answers = 0;
-                }
-            } else {
-                answers |= 1 << (c - b'a');
+        let mut answers = Answers::default();
+        for (i, c) in declaration.iter().enumerate() {
+            match alphabet.question(*c) {
+                Some(question) => answers.insert(question),
+                None => return Err(ParseError::syntax(line, i + 1, &[*c], alphabet.expected())),
             }
         }
-    }
-    if answers != 0 {
         result.push(Declaration { group, answers });
     }
 
-    result
+    Ok(result)
+}
+
+fn total(declarations: &[Declaration], query: Query) -> usize {
+    groups(declarations)
+        .map(|group| query.apply(&group).len())
+        .sum()
 }
 
 pub fn p1_solve(declarations: &[Declaration]) -> usize {
-    let mut sum = 0;
-    let mut group_answers: u32 = 0;
-    let mut group_index = 0;
-    for dec in declarations {
-        if dec.group != group_index {
-            sum += group_answers.count_ones();
-            group_index = dec.group;
-            group_answers = 0;
-        }
-        group_answers |= dec.answers;
-    }
-    sum += group_answers.count_ones();
-    sum as usize
+    total(declarations, Query::Anyone)
 }
 
 pub fn p2_solve(declarations: &[Declaration]) -> usize {
-    let mut sum = 0;
-    let mut group_answers: u32 = 0;
-    let mut group_index = 0;
-    let mut iter = declarations.iter();
-    if let Some(dec) = &mut iter.next() {
-        group_answers = dec.answers;
+    total(declarations, Query::Everyone)
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Declaration>;
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
     }
-    for dec in &mut iter {
-        if dec.group != group_index {
-            sum += group_answers.count_ones();
-            group_index = dec.group;
-            group_answers = dec.answers;
-        } else {
-            group_answers &= dec.answers;
-        }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        Some(p1_solve(input))
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        Some(p2_solve(input))
     }
-    sum += group_answers.count_ones();
-    sum as usize
 }
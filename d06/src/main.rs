@@ -1,24 +1,58 @@
 extern crate d06;
-use d06::{p1_solve, p2_solve, parse};
-use std::{env, io};
+use d06::{p1_solve, p2_solve, parse_with, Alphabet};
+use std::{env, io, process};
+
+const USAGE: &str = "usage: d06 <part> [--ignore-case | --alphabet <symbols>] < input.txt";
+
+fn parse_args(args: &[String]) -> Result<(u8, Alphabet), String> {
+    let mut iter = args.iter();
+    let part = match iter.next().map(|part| part.trim()) {
+        Some("1") => 1,
+        Some("2") => 2,
+        Some(part) => return Err(format!("invalid part: {}", part)),
+        None => return Err(String::from("missing part")),
+    };
+
+    let mut alphabet = None;
+    while let Some(arg) = iter.next() {
+        let next = match arg.as_str() {
+            "--ignore-case" => Alphabet::case_insensitive(),
+            "--alphabet" => {
+                let symbols = iter.next().ok_or(format!("{} requires a value", arg))?;
+                Alphabet::symbols(symbols.as_bytes()).map_err(|err| err.to_string())?
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        };
+        if alphabet.replace(next).is_some() {
+            return Err(String::from("only one alphabet can be given"));
+        }
+    }
+
+    Ok((part, alphabet.unwrap_or_default()))
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let args: Vec<String> = env::args().skip(1).collect();
 
-    let part = args
-        .get(1)
-        .expect("Missing argument 'part'")
-        .trim()
-        .parse()
-        .expect("Part must be a number");
+    let (part, alphabet) = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
 
-    let result = match part {
-        1 => Some(p1_solve(&parse(io::stdin().lock()))),
-        2 => Some(p2_solve(&parse(io::stdin().lock()))),
-        _ => None,
+    let parsed = match parse_with(io::stdin().lock(), &alphabet) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
     };
 
-    if let Some(result) = result {
-        println!("{}", result);
-    }
+    let result = match part {
+        1 => p1_solve(&parsed),
+        _ => p2_solve(&parsed),
+    };
+    println!("{}", result);
 }
@@ -0,0 +1,121 @@
+use crate::declaration::{Answers, Declaration};
+use std::num::NonZeroUsize;
+
+/// The declarations of the people in one group.
+#[derive(Clone, Copy, Debug)]
+pub struct Group<'a> {
+    declarations: &'a [Declaration],
+}
+
+impl<'a> Group<'a> {
+    /// The group's number, counting the blank lines before it.
+    pub fn index(&self) -> u16 {
+        self.declarations[0].group
+    }
+
+    /// How many people are in the group.
+    pub fn len(&self) -> usize {
+        self.declarations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+
+    pub fn declarations(&self) -> &'a [Declaration] {
+        self.declarations
+    }
+
+    /// The questions anyone in the group answered.
+    pub fn anyone(&self) -> Answers {
+        self.declarations
+            .iter()
+            .fold(Answers::default(), |answers, dec| answers | dec.answers)
+    }
+
+    /// The questions everyone in the group answered.
+    pub fn everyone(&self) -> Answers {
+        self.declarations
+            .iter()
+            .fold(Answers::all(), |answers, dec| answers & dec.answers)
+    }
+
+    /// How many people answered each question, for every question there can
+    /// be.
+    pub fn histogram(&self) -> Vec<usize> {
+        let mut counts = vec![0; Answers::QUESTIONS];
+        for dec in self.declarations {
+            for question in dec.answers.iter() {
+                counts[question] += 1;
+            }
+        }
+        counts
+    }
+
+    /// The questions at least `people` people answered. Asking for at least
+    /// nobody would take in questions that are not on the form, so `people`
+    /// can't be 0.
+    pub fn at_least(&self, people: NonZeroUsize) -> Answers {
+        let mut answers = Answers::default();
+        for (question, count) in self.histogram().into_iter().enumerate() {
+            if count >= people.get() {
+                answers.insert(question);
+            }
+        }
+        answers
+    }
+
+    /// The questions exactly one person answered.
+    pub fn exactly_one(&self) -> Answers {
+        let mut once = Answers::default();
+        let mut more = Answers::default();
+        for dec in self.declarations {
+            more = more | (once & dec.answers);
+            once = once | dec.answers;
+        }
+        once - more
+    }
+}
+
+/// Splits declarations into their groups, in input order.
+pub fn groups(declarations: &[Declaration]) -> impl Iterator<Item = Group<'_>> {
+    let mut rest = declarations;
+    std::iter::from_fn(move || {
+        let group = rest.first()?.group;
+        let len = rest
+            .iter()
+            .position(|dec| dec.group != group)
+            .unwrap_or(rest.len());
+        let (declarations, tail) = rest.split_at(len);
+        rest = tail;
+        Some(Group { declarations })
+    })
+}
+
+/// A question to ask of every group: which of the questions on the form the
+/// group answered, in some sense.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Query {
+    Anyone,
+    Everyone,
+    AtLeast(NonZeroUsize),
+    ExactlyOne,
+}
+
+impl Query {
+    pub fn apply(&self, group: &Group) -> Answers {
+        match self {
+            Query::Anyone => group.anyone(),
+            Query::Everyone => group.everyone(),
+            Query::AtLeast(people) => group.at_least(*people),
+            Query::ExactlyOne => group.exactly_one(),
+        }
+    }
+}
+
+/// The answer to the query for each group, in input order.
+pub fn query(declarations: &[Declaration], query: Query) -> Vec<Answers> {
+    groups(declarations)
+        .map(|group| query.apply(&group))
+        .collect()
+}
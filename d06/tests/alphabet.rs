@@ -0,0 +1,69 @@
+use d06::{groups, p1_solve, p2_solve, parse, parse_with, Alphabet, AlphabetError, Answers};
+
+#[test]
+fn lowercase_rejects_other_bytes() {
+    for (input, column, found) in &[
+        ("abC\n", 3, "C"),
+        ("1\n", 1, "1"),
+        ("a b\n", 2, " "),
+        ("a\rb\n", 2, "\r"),
+    ] {
+        let err = parse(input.as_bytes()).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "invalid input: line 1, column {}: expected answer a-z, found {:?}",
+                column, found
+            )
+        );
+    }
+}
+
+#[test]
+fn case_insensitive_letters_are_the_same_question() {
+    let declarations =
+        parse_with("aBc\nAbC\n\nZz\n".as_bytes(), &Alphabet::case_insensitive()).unwrap();
+    assert_eq!(p1_solve(&declarations), 4);
+    assert_eq!(p2_solve(&declarations), 4);
+    let err = parse_with("a1\n".as_bytes(), &Alphabet::case_insensitive())
+        .err()
+        .unwrap();
+    assert!(err
+        .to_string()
+        .ends_with("expected answer a-z or A-Z, found \"1\""));
+}
+
+#[test]
+fn symbols_can_be_any_bytes() {
+    let symbols: Vec<u8> = (b'!'..=b'~').collect();
+    let alphabet = Alphabet::symbols(&symbols).unwrap();
+    assert_eq!(alphabet.len(), 94);
+
+    let declarations = parse_with("aA0~\n~0b\n".as_bytes(), &alphabet).unwrap();
+    let group = groups(&declarations).next().unwrap();
+    assert_eq!(alphabet.spell(group.anyone()), "0Aab~");
+    assert_eq!(alphabet.spell(group.everyone()), "0~");
+    assert_eq!(alphabet.spell(group.exactly_one()), "Aab");
+}
+
+#[test]
+fn alphabets_must_fit_the_answers() {
+    let symbols: Vec<u8> = (0..=Answers::QUESTIONS as u8).collect();
+    assert_eq!(
+        Alphabet::symbols(&symbols),
+        Err(AlphabetError::TooMany(129))
+    );
+    assert_eq!(
+        Alphabet::symbols(b"abca"),
+        Err(AlphabetError::Repeated(b'a'))
+    );
+}
+
+#[test]
+fn groups_can_be_separated_by_crlf() {
+    let declarations = parse("abc\r\n\r\na\r\nb\r\nc\r\n\r\nab\r\nac".as_bytes()).unwrap();
+    let sizes: Vec<usize> = groups(&declarations).map(|group| group.len()).collect();
+    assert_eq!(sizes, vec![1, 3, 2]);
+    assert_eq!(p1_solve(&declarations), 9);
+    assert_eq!(p2_solve(&declarations), 4);
+}
@@ -0,0 +1,56 @@
+use d06::{groups, parse, query, Alphabet, Query};
+use std::num::NonZeroUsize;
+
+// the groups of sample.txt
+const SAMPLE: &str = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n";
+
+fn people(count: usize) -> NonZeroUsize {
+    NonZeroUsize::new(count).unwrap()
+}
+
+#[test]
+fn counts_how_many_people_answered_each_question() {
+    let alphabet = Alphabet::lowercase();
+    let declarations = parse("ab\nbc\nbcd\n".as_bytes()).unwrap();
+    let group = groups(&declarations).next().unwrap();
+    assert_eq!(group.len(), 3);
+
+    let histogram = group.histogram();
+    let counts: Vec<usize> = b"abcde"
+        .iter()
+        .map(|c| histogram[alphabet.question(*c).unwrap()])
+        .collect();
+    assert_eq!(counts, vec![1, 3, 2, 1, 0]);
+    assert_eq!(histogram.iter().sum::<usize>(), 7);
+
+    assert_eq!(alphabet.spell(group.at_least(people(1))), "abcd");
+    assert_eq!(alphabet.spell(group.at_least(people(2))), "bc");
+    assert_eq!(alphabet.spell(group.at_least(people(3))), "b");
+    assert!(group.at_least(people(4)).is_empty());
+    assert_eq!(alphabet.spell(group.exactly_one()), "ad");
+}
+
+#[test]
+fn at_least_matches_anyone_and_everyone_at_the_ends() {
+    let declarations = parse(SAMPLE.as_bytes()).unwrap();
+    for group in groups(&declarations) {
+        assert_eq!(group.at_least(people(1)), group.anyone());
+        assert_eq!(group.at_least(people(group.len())), group.everyone());
+    }
+}
+
+#[test]
+fn queries_every_group_of_the_sample() {
+    let alphabet = Alphabet::lowercase();
+    let declarations = parse(SAMPLE.as_bytes()).unwrap();
+    let spell = |asked| -> Vec<String> {
+        query(&declarations, asked)
+            .into_iter()
+            .map(|answers| alphabet.spell(answers))
+            .collect()
+    };
+    assert_eq!(spell(Query::Anyone), vec!["abc", "abc", "abc", "a", "b"]);
+    assert_eq!(spell(Query::Everyone), vec!["abc", "", "a", "a", "b"]);
+    assert_eq!(spell(Query::AtLeast(people(2))), vec!["", "", "a", "a", ""]);
+    assert_eq!(spell(Query::ExactlyOne), vec!["abc", "abc", "bc", "", "b"]);
+}
@@ -0,0 +1,142 @@
+use crate::{BagCountRule, BagCountRuleStatement};
+use std::collections::VecDeque;
+use ustr::{Ustr, UstrMap, UstrSet};
+
+/// The bag rules as a graph, with an edge from every bag to each bag it must
+/// contain. Colors without a rule of their own contain nothing.
+#[derive(Debug, Default)]
+pub struct BagGraph {
+    children: UstrMap<Vec<BagCountRule>>,
+    parents: UstrMap<Vec<Ustr>>,
+}
+
+impl BagGraph {
+    pub fn new(ruleset: &[BagCountRuleStatement]) -> Self {
+        let mut graph = BagGraph::default();
+        for (color, rules) in ruleset {
+            for rule in rules {
+                graph.parents.entry(rule.color).or_default().push(*color);
+            }
+            graph
+                .children
+                .entry(*color)
+                .or_default()
+                .extend(rules.iter().cloned());
+        }
+        graph
+    }
+
+    /// The bags a bag of this color must directly contain, and how many of
+    /// each.
+    pub fn children(&self, color: &str) -> &[BagCountRule] {
+        self.children
+            .get(&Ustr::from(color))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The colors of bags that must directly contain a bag of this color.
+    pub fn parents(&self, color: &str) -> &[Ustr] {
+        self.parents
+            .get(&Ustr::from(color))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Every color of bag that eventually contains a bag of this color.
+    pub fn ancestors(&self, color: &str) -> UstrSet {
+        self.reachable(Ustr::from(color), |color| {
+            self.parents.get(&color).into_iter().flatten().copied()
+        })
+    }
+
+    /// Every color of bag that a bag of this color eventually contains.
+    pub fn descendants(&self, color: &str) -> UstrSet {
+        self.reachable(Ustr::from(color), |color| {
+            self.children
+                .get(&color)
+                .into_iter()
+                .flatten()
+                .map(|rule| rule.color)
+        })
+    }
+
+    // the colors reachable from `start` over the edges, without `start` itself
+    // unless it is on a cycle
+    fn reachable<F, I>(&self, start: Ustr, next: F) -> UstrSet
+    where
+        F: Fn(Ustr) -> I,
+        I: Iterator<Item = Ustr>,
+    {
+        let mut seen = UstrSet::default();
+        let mut stack = vec![start];
+        while let Some(color) = stack.pop() {
+            for next in next(color) {
+                if seen.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// How many bags a bag of this color holds, all the way down. `None` if
+    /// the rules make a bag contain itself, or the count does not fit.
+    pub fn total_contained(&self, color: &str) -> Option<usize> {
+        let mut totals = UstrMap::default();
+        let mut visiting = UstrSet::default();
+        self.count(Ustr::from(color), &mut totals, &mut visiting)
+    }
+
+    fn count(
+        &self,
+        color: Ustr,
+        totals: &mut UstrMap<usize>,
+        visiting: &mut UstrSet,
+    ) -> Option<usize> {
+        if let Some(total) = totals.get(&color) {
+            return Some(*total);
+        }
+        if !visiting.insert(color) {
+            return None;
+        }
+        let mut total: usize = 0;
+        for rule in self.children.get(&color).into_iter().flatten() {
+            let inner = self.count(rule.color, totals, visiting)?;
+            total = inner
+                .checked_add(1)?
+                .checked_mul(rule.count as usize)?
+                .checked_add(total)?;
+        }
+        visiting.remove(&color);
+        totals.insert(color, total);
+        Some(total)
+    }
+
+    /// The shortest chain of colors from `from` down to `to`, each bag
+    /// directly containing the next, or `None` if a `from` bag never holds a
+    /// `to` bag. A bag is a chain of one to itself.
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<Ustr>> {
+        let (from, to) = (Ustr::from(from), Ustr::from(to));
+        let mut came_from = UstrMap::default();
+        let mut queue = VecDeque::new();
+        queue.push_back(from);
+        while let Some(color) = queue.pop_front() {
+            if color == to {
+                let mut path = vec![to];
+                let mut color = to;
+                while color != from {
+                    color = came_from[&color];
+                    path.push(color);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for rule in self.children.get(&color).into_iter().flatten() {
+                if rule.color != from && !came_from.contains_key(&rule.color) {
+                    came_from.insert(rule.color, color);
+                    queue.push_back(rule.color);
+                }
+            }
+        }
+        None
+    }
+}
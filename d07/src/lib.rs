@@ -1,38 +1,48 @@
+extern crate btoi;
 #[macro_use]
 extern crate nom;
 
+mod bag_graph;
+
+pub use bag_graph::BagGraph;
+use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
 use nom::character::is_digit;
 use std::io::BufRead;
 use std::str::from_utf8;
-use ustr::{Ustr, UstrMap, UstrSet};
+use ustr::Ustr;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct BagCountRule {
     pub count: u8,
     pub color: Ustr,
 }
 
-type BagCountRuleStatement = (Ustr, Vec<BagCountRule>);
+pub type BagCountRuleStatement = (Ustr, Vec<BagCountRule>);
 
+// the count is left as digits, so that parse_line can point at one that does
+// not fit
 named!(
-    bag_count_rule<BagCountRule>,
+    bag_count_rule<(&[u8], Ustr)>,
     do_parse!(
-        count: delimited!(char!(' '), take_while!(is_digit), char!(' '))
+        count: delimited!(char!(' '), take_while1!(is_digit), char!(' '))
             >> color: take_until!(" bag")
             >> pair!(tag!(" bag"), opt!(char!('s')))
-            >> (BagCountRule {
-                count: from_utf8(count).unwrap_or("").parse().unwrap_or(0),
-                color: Ustr::from(from_utf8(color).unwrap_or("undefined"))
-            })
+            >> (count, Ustr::from(from_utf8(color).unwrap_or("undefined")))
     )
 );
+// picks the branch from the first bytes rather than with alt!, which would only
+// report that neither of them matched at the start of the sequence
 named!(
-    bag_count_rule_sequence<Vec<BagCountRule>>,
-    terminated!(separated_list0!(char!(','), bag_count_rule), char!('.'))
+    bag_count_rule_sequence<Vec<(&[u8], Ustr)>>,
+    switch!(peek!(take!(2)),
+        b" n" => do_parse!(tag!(" no other bags.") >> (Vec::new()))
+        | _ => terminated!(separated_list1!(char!(','), bag_count_rule), char!('.'))
+    )
 );
 
 named!(
-    bag_count_rule_statement<BagCountRuleStatement>,
+    bag_count_rule_statement<(Ustr, Vec<(&[u8], Ustr)>)>,
     do_parse!(
         color: take_until!(" bags contain")
             >> tag!(" bags contain")
@@ -41,99 +51,75 @@ named!(
     )
 );
 
-pub fn parse<R>(mut reader: R) -> Vec<BagCountRuleStatement>
+fn parse_line(line: &[u8], line_no: usize) -> Result<BagCountRuleStatement, ParseError> {
+    let rest = match bag_count_rule_statement(line) {
+        Ok((&[], (color, rules))) => {
+            let rules = rules
+                .into_iter()
+                .map(|(digits, color)| match btoi(digits) {
+                    Ok(count) => Ok(BagCountRule { count, color }),
+                    Err(_) => Err(ParseError::within(
+                        line_no,
+                        line,
+                        digits,
+                        "count from 0 to 255",
+                    )),
+                })
+                .collect::<Result<_, _>>()?;
+            return Ok((color, rules));
+        }
+        Ok((rest, _)) => rest,
+        Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => err.input,
+        Err(nom::Err::Incomplete(_)) => &line[line.len()..],
+    };
+    Err(ParseError::syntax(
+        line_no,
+        line.len() - rest.len() + 1,
+        rest,
+        "\"<color> bags contain <count> <color> bags.\"",
+    ))
+}
+
+pub fn parse<R>(reader: R) -> Result<Vec<BagCountRuleStatement>, ParseError>
 where
     R: BufRead,
 {
     let mut result = Vec::new();
-    let mut buf: [u8; 4096] = [0; 4096];
-    let mut line_buf: [u8; 512] = [0; 512];
-    let mut line_len = 0;
-    while let Ok(len) = reader.read(&mut buf) {
-        if len == 0 {
-            break;
+    let mut lines = LineReader::new(reader);
+    while let Some((line, text)) = lines.next_line()? {
+        if !text.is_empty() {
+            result.push(parse_line(text, line)?);
         }
-        for c in buf[..len].iter() {
-            if c == &b'\n' {
-                if let Ok((_, r)) = bag_count_rule_statement(&line_buf[..line_len]) {
-                    result.push(r);
-                }
-                line_buf = [0; 512];
-                line_len = 0;
-            } else {
-                line_buf[line_len] = *c;
-                line_len += 1;
-            }
-        }
-    }
-
-    if let Ok((_, r)) = bag_count_rule_statement(&line_buf[..line_len]) {
-        result.push(r);
     }
-    result
+    Ok(result)
 }
 
+/// The color of bag the puzzle asks about.
+pub const SHINY_GOLD: &str = "shiny gold";
+
 pub fn p1_solve(ruleset: &[BagCountRuleStatement]) -> usize {
-    let mut child_2_parent = UstrMap::default();
-    for (color, rules) in ruleset {
-        for rule in rules {
-            let parents = child_2_parent.entry(rule.color).or_insert_with(Vec::new);
-            parents.push(color);
-        }
-    }
-    count_parents(
-        &Ustr::from("shiny gold"),
-        &child_2_parent,
-        UstrSet::default(),
-    )
-    .len()
+    BagGraph::new(ruleset).ancestors(SHINY_GOLD).len()
 }
 
-fn count_parents(color: &Ustr, child_2_parent: &UstrMap<Vec<&Ustr>>, set: UstrSet) -> UstrSet {
-    if let Some(parents) = child_2_parent.get(color) {
-        return parents.iter().fold(set, |mut acc, parent| {
-            if acc.contains(parent) {
-                return acc;
-            }
-            acc.insert(**parent);
-            count_parents(parent, child_2_parent, acc)
-        });
-    }
-    set
+pub fn p2_solve(ruleset: &[BagCountRuleStatement]) -> Option<usize> {
+    BagGraph::new(ruleset).total_contained(SHINY_GOLD)
 }
 
-pub fn p2_solve(ruleset: &[BagCountRuleStatement]) -> usize {
-    let mut rule_map = UstrMap::default();
-    for (color, rules) in ruleset {
-        rule_map.insert(Ustr::from(color), rules.as_slice());
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<BagCountRuleStatement>;
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
     }
-    *count_children(&Ustr::from("shiny gold"), &rule_map, UstrMap::default())
-        .get(&Ustr::from("shiny gold"))
-        .unwrap_or(&0)
-}
 
-fn count_children(
-    color: &Ustr,
-    rule_map: &UstrMap<&[BagCountRule]>,
-    mut counts: UstrMap<usize>,
-) -> UstrMap<usize> {
-    if counts.contains_key(color) {
-        return counts;
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        Some(p1_solve(input))
     }
-    if let Some(rules) = rule_map.get(color) {
-        counts = rules.iter().fold(counts, |mut acc, rule| {
-            acc = count_children(&rule.color, rule_map, acc);
-            acc
-        });
-        counts.insert(
-            *color,
-            rules.iter().fold(0, |mut acc, rule| {
-                let sum = counts.get(&rule.color).unwrap_or(&0) + 1;
-                let c = rule.count as usize;
-                acc += sum * c;
-                acc
-            }),
-        );
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
     }
-    counts
 }
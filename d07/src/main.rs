@@ -1,24 +1,63 @@
 extern crate d07;
-use d07::{p1_solve, p2_solve, parse};
-use std::{env, io};
+use d07::{parse, BagGraph, SHINY_GOLD};
+use std::{env, io, process};
+
+const USAGE: &str = "usage: d07 <part> [--color <color>] < input.txt";
+
+fn parse_args(args: &[String]) -> Result<(u8, String), String> {
+    let mut iter = args.iter();
+    let part = match iter.next().map(|part| part.trim()) {
+        Some("1") => 1,
+        Some("2") => 2,
+        Some(part) => return Err(format!("invalid part: {}", part)),
+        None => return Err(String::from("missing part")),
+    };
+
+    let mut color = String::from(SHINY_GOLD);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--color" => {
+                color = iter
+                    .next()
+                    .ok_or(format!("{} requires a value", arg))?
+                    .trim()
+                    .to_string()
+            }
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok((part, color))
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    let part = args
-        .get(1)
-        .expect("Missing argument 'part'")
-        .trim()
-        .parse()
-        .expect("Part must be a number");
-
-    let result = match part {
-        1 => Some(p1_solve(&parse(io::stdin().lock()))),
-        2 => Some(p2_solve(&parse(io::stdin().lock()))),
-        _ => None,
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let (part, color) = match parse_args(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let parsed = match parse(io::stdin().lock()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
     };
 
-    if let Some(result) = result {
-        println!("{}", result);
+    let graph = BagGraph::new(&parsed);
+    match part {
+        1 => println!("{}", graph.ancestors(&color).len()),
+        _ => match graph.total_contained(&color) {
+            Some(total) => println!("{}", total),
+            None => {
+                eprintln!("a {} bag holds itself or too many bags to count", color);
+                process::exit(1);
+            }
+        },
     }
 }
@@ -0,0 +1,106 @@
+use d07::{parse, BagGraph, SHINY_GOLD};
+use std::fs::File;
+use std::io::BufReader;
+use ustr::Ustr;
+
+fn sample() -> BagGraph {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/sample.txt")).unwrap();
+    BagGraph::new(&parse(BufReader::new(file)).unwrap())
+}
+
+fn from_rules(rules: &str) -> BagGraph {
+    BagGraph::new(&parse(rules.as_bytes()).unwrap())
+}
+
+fn colors(path: &[Ustr]) -> Vec<&str> {
+    path.iter().map(Ustr::as_str).collect()
+}
+
+#[test]
+fn answers_the_sample() {
+    let graph = sample();
+    let mut ancestors: Vec<&str> = graph
+        .ancestors(SHINY_GOLD)
+        .iter()
+        .map(Ustr::as_str)
+        .collect();
+    ancestors.sort_unstable();
+    assert_eq!(
+        ancestors,
+        vec!["bright white", "dark orange", "light red", "muted yellow"]
+    );
+    assert_eq!(graph.total_contained(SHINY_GOLD), Some(32));
+    assert_eq!(graph.total_contained("faded blue"), Some(0));
+    assert_eq!(graph.descendants(SHINY_GOLD).len(), 4);
+}
+
+#[test]
+fn a_bag_that_contains_itself_has_no_total() {
+    let graph = from_rules(
+        "shiny gold bags contain 2 dark red bags.\n\
+         dark red bags contain 1 shiny gold bag, 3 faded blue bags.\n\
+         faded blue bags contain no other bags.\n",
+    );
+    assert_eq!(graph.total_contained(SHINY_GOLD), None);
+    assert_eq!(graph.total_contained("faded blue"), Some(0));
+    assert!(graph
+        .ancestors(SHINY_GOLD)
+        .contains(&Ustr::from(SHINY_GOLD)));
+
+    let itself = from_rules("shiny gold bags contain 1 shiny gold bag.\n");
+    assert_eq!(itself.total_contained(SHINY_GOLD), None);
+}
+
+#[test]
+fn finds_the_shortest_path_down() {
+    let graph = sample();
+    let path = graph.path("light red", "faded blue").unwrap();
+    assert_eq!(
+        colors(&path),
+        vec!["light red", "muted yellow", "faded blue"]
+    );
+    let path = graph.path("dark orange", "dotted black").unwrap();
+    assert_eq!(path.len(), 5);
+    assert_eq!(path.first().map(Ustr::as_str), Some("dark orange"));
+    assert_eq!(path.last().map(Ustr::as_str), Some("dotted black"));
+
+    // bags only lead down, never up
+    assert_eq!(graph.path("faded blue", "light red"), None);
+    assert_eq!(graph.path(SHINY_GOLD, "no such color"), None);
+}
+
+#[test]
+fn a_bag_is_a_path_of_one_to_itself() {
+    let graph = sample();
+    assert_eq!(
+        graph.path(SHINY_GOLD, SHINY_GOLD).as_deref().map(colors),
+        Some(vec![SHINY_GOLD])
+    );
+    assert_eq!(
+        graph
+            .path("no such color", "no such color")
+            .as_deref()
+            .map(colors),
+        Some(vec!["no such color"])
+    );
+}
+
+#[test]
+fn unknown_colors_have_no_parents_or_children() {
+    let graph = sample();
+    assert!(graph.parents("no such color").is_empty());
+    assert!(graph.children("no such color").is_empty());
+    assert!(graph.ancestors("no such color").is_empty());
+    assert!(graph.descendants("no such color").is_empty());
+    assert_eq!(graph.total_contained("no such color"), Some(0));
+
+    // a color that is only ever contained has parents but no children
+    assert!(graph.children("faded blue").is_empty());
+    assert_eq!(graph.parents("faded blue").len(), 3);
+    let children: Vec<(u8, &str)> = graph
+        .children(SHINY_GOLD)
+        .iter()
+        .map(|rule| (rule.count, rule.color.as_str()))
+        .collect();
+    assert_eq!(children, vec![(1, "dark olive"), (2, "vibrant plum")]);
+}
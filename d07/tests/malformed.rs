@@ -0,0 +1,37 @@
+use d07::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn points_at_counts_that_do_not_fit() {
+    assert_eq!(
+        error("faded blue bags contain no other bags.\nlight red bags contain 300 bright white bags.\n"),
+        "invalid input: line 2, column 24: expected count from 0 to 255, found \"300\""
+    );
+    assert_eq!(
+        error("light red bags contain 1 bright white bag, 256 muted yellow bags.\n"),
+        "invalid input: line 1, column 44: expected count from 0 to 255, found \"256\""
+    );
+}
+
+#[test]
+fn points_at_where_a_rule_stops_making_sense() {
+    assert_eq!(
+        error("faded blue bags contain no other bags\n"),
+        "invalid input: line 1, column 38: expected \"<color> bags contain <count> <color> bags.\", found end of line"
+    );
+    assert_eq!(
+        error("shiny gold bags contain two dark red bags.\n"),
+        "invalid input: line 1, column 25: expected \"<color> bags contain <count> <color> bags.\", found \"two dark red bags.\""
+    );
+    assert_eq!(
+        error("shiny gold bags contain 2 dark red bags;\n"),
+        "invalid input: line 1, column 40: expected \"<color> bags contain <count> <color> bags.\", found \";\""
+    );
+    assert_eq!(
+        error("shiny gold bags contain 2 dark red bags. \n"),
+        "invalid input: line 1, column 41: expected \"<color> bags contain <count> <color> bags.\", found \" \""
+    );
+}
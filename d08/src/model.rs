@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use common::ParseError;
 use std::str::from_utf8;
 
 #[derive(Debug, Clone)]
@@ -32,34 +32,33 @@ impl Instruction {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for Instruction {
-    type Error = &'static str;
-
-    fn try_from(v: &'a [u8]) -> Result<Self, &'static str> {
-        if v.len() < 6 {
-            return Err("line too short");
-        }
-        let operation = match &v[..3] {
-            b"nop" => Some(Operation::Nop),
-            b"jmp" => Some(Operation::Jmp),
-            b"acc" => Some(Operation::Acc),
-            _ => None,
+impl Instruction {
+    pub fn parse(v: &[u8], line: usize) -> Result<Self, ParseError> {
+        let operation = match &v[..v.len().min(3)] {
+            b"nop" => Operation::Nop,
+            b"jmp" => Operation::Jmp,
+            b"acc" => Operation::Acc,
+            found => return Err(ParseError::syntax(line, 1, found, "nop, jmp or acc")),
         };
-        let multiplier = match &v[4] {
-            b'-' => -1,
-            _ => 1,
+        let separator = &v[3..v.len().min(4)];
+        if separator != b" " {
+            return Err(ParseError::syntax(line, 4, separator, "' '"));
+        }
+        let multiplier = match &v[4..v.len().min(5)] {
+            b"-" => -1,
+            b"+" => 1,
+            found => return Err(ParseError::syntax(line, 5, found, "'+' or '-'")),
         };
+        // the sign has been read already, so the value has to start with a digit
         let value = from_utf8(&v[5..])
             .ok()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(0)
+            .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+            .and_then(|v| v.parse::<i32>().ok())
+            .ok_or_else(|| ParseError::syntax(line, 6, &v[5..], "number"))?
             * multiplier;
 
-        if operation.is_none() {
-            return Err("unsupported operation");
-        }
         Ok(Instruction {
-            operation: operation.unwrap(),
+            operation,
             value,
             executed: false,
         })
@@ -75,7 +74,7 @@ pub struct Program {
 
 impl Program {
     pub fn run(mut self) -> Self {
-        while let Some(mut instruction) = self.instructions.get_mut(self.cursor) {
+        while let Some(instruction) = self.instructions.get_mut(self.cursor) {
             if instruction.executed {
                 break;
             }
@@ -85,7 +84,7 @@ impl Program {
         self
     }
     pub fn run_tracked(mut self, steps: &mut Vec<usize>) -> Self {
-        while let Some(mut instruction) = self.instructions.get_mut(self.cursor) {
+        while let Some(instruction) = self.instructions.get_mut(self.cursor) {
             if instruction.executed {
                 break;
             }
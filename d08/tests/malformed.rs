@@ -0,0 +1,52 @@
+use d08::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn points_at_each_part_of_an_instruction() {
+    assert_eq!(
+        error("nop +0\nmul +1\n"),
+        "invalid input: line 2, column 1: expected nop, jmp or acc, found \"mul\""
+    );
+    assert_eq!(
+        error("jm\n"),
+        "invalid input: line 1, column 1: expected nop, jmp or acc, found \"jm\""
+    );
+    assert_eq!(
+        error("nop\n"),
+        "invalid input: line 1, column 4: expected ' ', found end of line"
+    );
+    assert_eq!(
+        error("acc 5\n"),
+        "invalid input: line 1, column 5: expected '+' or '-', found \"5\""
+    );
+    assert_eq!(
+        error("jmp +x\n"),
+        "invalid input: line 1, column 6: expected number, found \"x\""
+    );
+    assert_eq!(
+        error("acc -\n"),
+        "invalid input: line 1, column 6: expected number, found end of line"
+    );
+}
+
+#[test]
+fn takes_one_sign_only() {
+    assert_eq!(
+        error("acc +-5\n"),
+        "invalid input: line 1, column 6: expected number, found \"-5\""
+    );
+    assert_eq!(
+        error("acc ++5\n"),
+        "invalid input: line 1, column 6: expected number, found \"+5\""
+    );
+    assert_eq!(
+        parse("acc -5\n\nnop +0\n".as_bytes())
+            .unwrap()
+            .instructions
+            .len(),
+        2
+    );
+}
@@ -1,16 +1,23 @@
+use common::bench::bench_parts;
+use common::inputs;
 use criterion::{criterion_group, criterion_main, Criterion};
-use d09::{p1_solve, p2_solve, parse};
-use std::io::{stdin, Read};
+use d09::{p1_solve, p2_solve, parse, Day09};
 
 fn main_benchmark(c: &mut Criterion) {
-    let mut buffer = String::new();
-    if stdin().lock().read_to_string(&mut buffer).is_ok() {
-        c.bench_function("d09 parse", |b| b.iter(|| parse(buffer.as_bytes())));
-        let parsed = parse(buffer.as_bytes());
-        c.bench_function("d09 p1 (248131121)", |b| b.iter(|| p1_solve(&parsed, 25)));
-        c.bench_function("d09 p2 (31580383)", |b| {
-            b.iter(|| p2_solve(&parsed, 248131121))
-        });
+    let dir = env!("CARGO_MANIFEST_DIR");
+    // Day09 solves with the real input's preamble of 25, but the samples use
+    // a shorter one, so their parts are benchmarked here instead
+    bench_parts::<Day09, _>(c, dir, |name, _| name == "input.txt");
+    for (name, input) in inputs(dir).unwrap() {
+        if name == "input.txt" {
+            continue;
+        }
+        let mut group = c.benchmark_group(format!("d09 {}", name));
+        let parsed = parse(input.as_slice()).unwrap();
+        group.bench_function("p1", |b| b.iter(|| p1_solve(&parsed, 5)));
+        let invalid = p1_solve(&parsed, 5).unwrap();
+        group.bench_function("p2", |b| b.iter(|| p2_solve(&parsed, invalid)));
+        group.finish();
     }
 }
 
@@ -1,49 +1,32 @@
 extern crate btoi;
 
 use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
 use std::collections::VecDeque;
 use std::io::BufRead;
 
-pub fn parse<R>(mut reader: R) -> Vec<u64>
+fn parse_line(line_buf: &[u8], line: usize) -> Result<u64, ParseError> {
+    btoi(line_buf).map_err(|_| ParseError::syntax(line, 1, line_buf, "number"))
+}
+
+pub fn parse<R>(reader: R) -> Result<Vec<u64>, ParseError>
 where
     R: BufRead,
 {
     let mut result = Vec::new();
-    let mut page: [u8; 4096] = [0; 4096];
-    let mut line_buf: [u8; 32] = [0; 32];
-    let mut line_len = 0;
-    let mut pages_read: usize = 0;
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        result.reserve(result.len().checked_div(pages_read).unwrap_or(page_len / 2));
-        for c in page[..page_len].iter() {
-            if c == &b'\n' {
-                if let Ok(int) = btoi(&line_buf[..line_len]) {
-                    result.push(int);
-                }
-                line_buf = [0; 32];
-                line_len = 0;
-            } else if line_len < 32 {
-                line_buf[line_len] = *c;
-                line_len += 1;
-            }
-        }
-        pages_read += 1;
-    }
-    if line_len > 0 {
-        if let Ok(int) = btoi(&line_buf[..line_len]) {
-            result.push(int);
+    let mut lines = LineReader::new(reader);
+    while let Some((line, text)) = lines.next_line()? {
+        if !text.is_empty() {
+            result.push(parse_line(text, line)?);
         }
     }
     result.shrink_to_fit();
-    result
+    Ok(result)
 }
 
 pub fn p1_solve(input: &[u64], preamble_len: usize) -> Option<u64> {
     let mut preamble = VecDeque::with_capacity(preamble_len);
-    for val in input[..preamble_len].iter() {
+    for val in input.get(..preamble_len)?.iter() {
         preamble.push_back(*val);
     }
     for val in input[preamble_len..].iter() {
@@ -97,3 +80,22 @@ pub fn p2_solve(input: &[u64], target: u64) -> Option<u64> {
     }
     None
 }
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<u64>;
+    type Output = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input, 25)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input, 25).and_then(|target| p2_solve(input, target))
+    }
+}
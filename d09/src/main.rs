@@ -1,6 +1,6 @@
 extern crate d09;
 use d09::{p1_solve, p2_solve, parse};
-use std::{env, io};
+use std::{env, io, process};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -12,9 +12,17 @@ fn main() {
         .parse()
         .expect("Part must be a number");
 
+    let parsed = match parse(io::stdin().lock()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+
     let result = match part {
-        1 => p1_solve(&parse(io::stdin().lock()), 25),
-        2 => p2_solve(&parse(io::stdin().lock()), 248131121),
+        1 => p1_solve(&parsed, 25),
+        2 => p2_solve(&parsed, 248131121),
         _ => None,
     };
 
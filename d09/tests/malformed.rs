@@ -0,0 +1,17 @@
+use d09::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn rejects_lines_that_are_not_numbers() {
+    assert_eq!(
+        error("35\n20\n\n-15\n"),
+        "invalid input: line 4, column 1: expected number, found \"-15\""
+    );
+    assert_eq!(
+        error("35\n2 0\n"),
+        "invalid input: line 2, column 1: expected number, found \"2 0\""
+    );
+}
@@ -1,48 +1,58 @@
 extern crate btoi;
 
 use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
 use std::io::BufRead;
 
-pub fn parse<R>(mut reader: R) -> Vec<bool>
+fn parse_line(line_buf: &[u8], line: usize, result: &mut Vec<bool>) -> Result<(), ParseError> {
+    match btoi::<usize>(line_buf) {
+        Ok(int) if int > 0 => {
+            if int > result.len() {
+                result.resize(int, false);
+            }
+            result[int - 1] = true;
+            Ok(())
+        }
+        _ => Err(ParseError::syntax(line, 1, line_buf, "positive number")),
+    }
+}
+
+pub fn parse<R>(reader: R) -> Result<Vec<bool>, ParseError>
 where
     R: BufRead,
 {
     let mut result = Vec::new();
-    let mut page: [u8; 4096] = [0; 4096];
-    let mut line_buf: [u8; 32] = [0; 32];
-    let mut line_len = 0;
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        for c in page[..page_len].iter() {
-            if c == &b'\n' {
-                if let Ok(int) = btoi::<usize>(&line_buf[..line_len]) {
-                    if int > result.len() {
-                        result.resize(int, false);
-                    }
-                    result[int - 1] = true;
-                }
-                line_buf = [0; 32];
-                line_len = 0;
-            } else if line_len < 32 {
-                line_buf[line_len] = *c;
-                line_len += 1;
-            }
+    let mut lines = LineReader::new(reader);
+    while let Some((line, text)) = lines.next_line()? {
+        if !text.is_empty() {
+            parse_line(text, line, &mut result)?;
         }
     }
-    if line_len > 0 {
-        if let Ok(int) = btoi::<usize>(&line_buf[..line_len]) {
-            if int > result.len() {
-                result.resize(int, false);
-            }
-            result[int - 1] = true;
+    Ok(result)
+}
+
+// the unrolled walk in p1_solve looks up to three adapters ahead, so bags
+// shorter than that are counted one gap at a time
+fn p1_solve_short(bag_of_adapters: &[bool]) -> Option<u64> {
+    let mut ones = 0;
+    let mut threes = 1;
+    let mut last = 0;
+    for (i, _) in bag_of_adapters.iter().enumerate().filter(|(_, a)| **a) {
+        match i + 1 - last {
+            1 => ones += 1,
+            2 => (),
+            3 => threes += 1,
+            _ => return None,
         }
+        last = i + 1;
     }
-    result
+    Some(ones * threes)
 }
 
 pub fn p1_solve(bag_of_adapters: &[bool]) -> Option<u64> {
+    if bag_of_adapters.len() < 3 {
+        return p1_solve_short(bag_of_adapters);
+    }
     let mut ones = 0;
     let mut threes = 1;
     let mut i = 0;
@@ -111,3 +121,22 @@ pub fn p2_solve(bag_of_adapters: &[bool]) -> Option<u64> {
     }
     Some(last1 + last2 + last3)
 }
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<bool>;
+    type Output = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
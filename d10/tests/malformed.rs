@@ -0,0 +1,21 @@
+use d10::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn rejects_adapters_that_are_not_positive() {
+    assert_eq!(
+        error("16\n10\n\n0\n"),
+        "invalid input: line 4, column 1: expected positive number, found \"0\""
+    );
+    assert_eq!(
+        error("16\n-1\n"),
+        "invalid input: line 2, column 1: expected positive number, found \"-1\""
+    );
+    assert_eq!(
+        error("1x\n"),
+        "invalid input: line 1, column 1: expected positive number, found \"1x\""
+    );
+}
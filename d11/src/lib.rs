@@ -1,3 +1,4 @@
+use common::{LineReader, ParseError, Solution};
 use std::io::BufRead;
 
 #[derive(Debug)]
@@ -7,39 +8,47 @@ pub struct SeatMap {
     width: usize,
 }
 
-pub fn parse<R>(mut reader: R) -> SeatMap
+pub fn parse<R>(reader: R) -> Result<SeatMap, ParseError>
 where
     R: BufRead,
 {
     let mut seats = Vec::new();
-    let mut width = 0;
-    let mut buf: [u8; 4096] = [0; 4096];
-    while let Ok(len) = reader.read(&mut buf) {
-        if len == 0 {
-            break;
+    let mut width = None;
+    let mut last_line = 0;
+    let mut lines = LineReader::new(reader);
+    while let Some((line, row)) = lines.next_line()? {
+        last_line = line;
+        if row.is_empty() {
+            continue;
         }
-        seats.reserve(len);
-        for c in buf[..len].iter() {
-            match &c {
-                b'\n' => {
-                    if width == 0 {
-                        width = seats.len();
-                    }
+        for (i, c) in row.iter().enumerate() {
+            match c {
+                _ if width.is_some_and(|width| i >= width) => {
+                    return Err(ParseError::syntax(line, i + 1, &[*c], "end of row"));
                 }
                 b'L' => seats.push(true),
                 b'.' => seats.push(false),
-                _ => (), // ignore
+                _ => return Err(ParseError::syntax(line, i + 1, &[*c], "'L' or '.'")),
             }
         }
+        match width {
+            Some(width) if row.len() < width => {
+                return Err(ParseError::syntax(line, row.len() + 1, b"", "'L' or '.'"));
+            }
+            Some(_) => (),
+            None => width = Some(row.len()),
+        }
     }
+    // a grid needs at least one row to have a width
+    let width = width.ok_or_else(|| ParseError::syntax(last_line + 1, 1, b"", "'L' or '.'"))?;
     seats.shrink_to_fit();
     let mut occupied = Vec::new();
     occupied.resize(seats.len(), false);
-    SeatMap {
+    Ok(SeatMap {
         seats: seats.into_boxed_slice(),
         occupied: occupied.into_boxed_slice(),
         width,
-    }
+    })
 }
 
 pub fn p1_solve(seat_map: &SeatMap) -> Option<u64> {
@@ -54,7 +63,8 @@ pub fn p1_solve(seat_map: &SeatMap) -> Option<u64> {
     Some(result)
 }
 
-pub fn p1_iterate(seats: &[bool], occupied: &Box<[bool]>, width: &usize) -> Box<[bool]> {
+#[allow(clippy::explicit_counter_loop)]
+pub fn p1_iterate(seats: &[bool], occupied: &[bool], width: &usize) -> Box<[bool]> {
     let mut col = 0;
     let mut row = 0;
     let mut change_counter = 0;
@@ -69,7 +79,7 @@ pub fn p1_iterate(seats: &[bool], occupied: &Box<[bool]>, width: &usize) -> Box<
     let mut br = bc + 1;
     let height = seats.len() / *width;
 
-    let mut result = occupied.clone();
+    let mut result = Box::<[bool]>::from(occupied);
 
     for (i, s) in seats.iter().enumerate() {
         if *s {
@@ -180,13 +190,13 @@ pub enum Direction {
     BottomRight,
 }
 
-pub fn p2_iterate(seats: &[bool], occupied: &Box<[bool]>, width: &usize) -> Box<[bool]> {
+pub fn p2_iterate(seats: &[bool], occupied: &[bool], width: &usize) -> Box<[bool]> {
     let mut col = 0;
     let mut row = 0;
     let mut change_counter = 0;
     let height = seats.len() / *width;
 
-    let mut result = occupied.clone();
+    let mut result = Box::<[bool]>::from(occupied);
 
     for (i, s) in seats.iter().enumerate() {
         if *s {
@@ -331,3 +341,22 @@ pub fn p2_seek(
     }
     occupied[pos]
 }
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = SeatMap;
+    type Output = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
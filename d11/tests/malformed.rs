@@ -0,0 +1,33 @@
+use d11::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn rejects_ragged_rows_and_unknown_cells() {
+    assert_eq!(
+        error("L.L\nL.LL\n"),
+        "invalid input: line 2, column 4: expected end of row, found \"L\""
+    );
+    assert_eq!(
+        error("L.L\n\nL.\n"),
+        "invalid input: line 3, column 3: expected 'L' or '.', found end of line"
+    );
+    assert_eq!(
+        error("L.L\nL#L\n"),
+        "invalid input: line 2, column 2: expected 'L' or '.', found \"#\""
+    );
+}
+
+#[test]
+fn needs_at_least_one_row() {
+    assert_eq!(
+        error(""),
+        "invalid input: line 1, column 1: expected 'L' or '.', found end of line"
+    );
+    assert_eq!(
+        error("\n\n"),
+        "invalid input: line 3, column 1: expected 'L' or '.', found end of line"
+    );
+}
@@ -1,7 +1,7 @@
 extern crate btoi;
 
 use btoi::btoi;
-use std::convert::TryFrom;
+use common::{LineReader, ParseError, Solution};
 use std::fmt;
 use std::io::BufRead;
 
@@ -55,27 +55,21 @@ impl fmt::Display for Action {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for Instruction {
-    type Error = &'static str;
-
-    fn try_from(v: &'a [u8]) -> Result<Self, &'static str> {
-        if let Some(action) = v.get(0).and_then(|first_byte| match first_byte {
-            b'N' => Some(Action::North),
-            b'S' => Some(Action::South),
-            b'E' => Some(Action::East),
-            b'W' => Some(Action::West),
-            b'L' => Some(Action::Left),
-            b'R' => Some(Action::Right),
-            b'F' => Some(Action::Forward),
-            _ => None,
-        }) {
-            if let Ok(value) = btoi(&v[1..]) {
-                Ok(Instruction { action, value })
-            } else {
-                Err("value expected")
-            }
-        } else {
-            Err("instruction expected")
+impl Instruction {
+    pub fn parse(v: &[u8], line: usize) -> Result<Self, ParseError> {
+        let action = match v.first() {
+            Some(b'N') => Action::North,
+            Some(b'S') => Action::South,
+            Some(b'E') => Action::East,
+            Some(b'W') => Action::West,
+            Some(b'L') => Action::Left,
+            Some(b'R') => Action::Right,
+            Some(b'F') => Action::Forward,
+            _ => return Err(ParseError::syntax(line, 1, &v[..v.len().min(1)], "action")),
+        };
+        match btoi(&v[1..]) {
+            Ok(value) => Ok(Instruction { action, value }),
+            Err(_) => Err(ParseError::syntax(line, 2, &v[1..], "value")),
         }
     }
 }
@@ -86,40 +80,18 @@ impl fmt::Display for Instruction {
     }
 }
 
-pub fn parse<R>(mut reader: R) -> Vec<Instruction>
+pub fn parse<R>(reader: R) -> Result<Vec<Instruction>, ParseError>
 where
     R: BufRead,
 {
     let mut result = Vec::new();
-    let mut page: [u8; 4096] = [0; 4096];
-    let mut line_buf: [u8; 8] = [0; 8];
-    let mut line_len = 0;
-    let mut pages_read: usize = 0;
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        result.reserve(result.len().checked_div(pages_read).unwrap_or(page_len / 2));
-        for c in page[..page_len].iter() {
-            if c == &b'\n' {
-                if let Ok(instruction) = Instruction::try_from(&line_buf[..line_len]) {
-                    result.push(instruction);
-                }
-                line_buf = [0; 8];
-                line_len = 0;
-            } else if line_len < 8 {
-                line_buf[line_len] = *c;
-                line_len += 1;
-            }
-        }
-        pages_read += 1;
-    }
-    if line_len > 0 {
-        if let Ok(instruction) = Instruction::try_from(&line_buf[..line_len]) {
-            result.push(instruction);
+    let mut lines = LineReader::new(reader);
+    while let Some((line, text)) = lines.next_line()? {
+        if !text.is_empty() {
+            result.push(Instruction::parse(text, line)?);
         }
     }
-    result
+    Ok(result)
 }
 
 pub fn rotate_left(orientation: Orientation) -> Orientation {
@@ -259,3 +231,22 @@ pub fn p2_solve(instructions: &[Instruction]) -> Option<usize> {
 
     Some((north.abs() + east.abs()) as usize)
 }
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Instruction>;
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
@@ -0,0 +1,21 @@
+use d12::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn points_at_the_action_or_its_value() {
+    assert_eq!(
+        error("F10\nN3\n\nX7\n"),
+        "invalid input: line 4, column 1: expected action, found \"X\""
+    );
+    assert_eq!(
+        error("F10\nR\n"),
+        "invalid input: line 2, column 2: expected value, found end of line"
+    );
+    assert_eq!(
+        error("F1 0\n"),
+        "invalid input: line 1, column 2: expected value, found \"1 0\""
+    );
+}
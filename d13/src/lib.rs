@@ -1,103 +1,77 @@
 extern crate btoi;
 
-use btoi::{btoi, ParseIntegerError};
+use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
 use std::collections::HashMap;
 use std::io::BufRead;
 
-fn parse_part(result: &mut Vec<Option<i64>>, buffer: &[u8]) {
-    if let Ok(int) = btoi::<i64>(buffer) {
-        result.push(Some(int));
+fn parse_bus(field: &[u8], line: usize, column: usize) -> Result<Option<i64>, ParseError> {
+    if field == b"x" {
+        return Ok(None);
     }
-}
-
-fn parse_page(
-    iter: std::slice::Iter<u8>,
-    mut result: &mut Vec<Option<i64>>,
-    buffer: &mut [u8; 16],
-    len: &mut usize,
-) {
-    for c in iter {
-        match c {
-            &b'\n' | &b',' => {
-                parse_part(&mut result, &buffer[..*len]);
-                *len = 0;
-            }
-            &b'x' => {
-                result.push(None);
-                *len = 0;
-            }
-            _ => {
-                buffer[*len] = *c;
-                *len += 1;
-            }
-        }
+    match btoi::<i64>(field) {
+        Ok(number) if number > 0 => Ok(Some(number)),
+        Ok(_) => Err(ParseError::syntax(
+            line,
+            column,
+            field,
+            "positive bus number",
+        )),
+        Err(_) => Err(ParseError::syntax(line, column, field, "bus number or 'x'")),
     }
 }
 
-pub fn parse<R>(mut reader: R) -> Option<(i64, Vec<Option<i64>>)>
+pub fn parse<R>(reader: R) -> Result<(i64, Vec<Option<i64>>), ParseError>
 where
     R: BufRead,
 {
+    let mut lines = LineReader::new(reader);
+
+    // the first line is the earliest departure timestamp
+    let (line, first) = lines.next_line()?.unwrap_or((1, b""));
+    let earliest_departure =
+        btoi::<i64>(first).map_err(|_| ParseError::syntax(line, 1, first, "timestamp"))?;
+
+    // the second line lists bus numbers, 'x' for ones out of service
+    let (line, second) = lines.next_line()?.unwrap_or((2, b""));
     let mut result = Vec::new();
-    let mut page: [u8; 4096] = [0; 4096];
-    let mut part_buffer: [u8; 16] = [0; 16];
-    let mut part_len = 0;
-
-    let mut some_earliest_departure: Option<i64> = None;
-    if let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            return None;
-        }
-        let mut shared_iter = page[..page_len].iter();
-        // part 1: read the beginning timestamp
-        for c in &mut shared_iter {
-            if c == &b'\n' {
-                if let Ok(earliest_departure) = btoi::<i64>(&part_buffer[..part_len]) {
-                    some_earliest_departure = Some(earliest_departure);
-                }
-                part_len = 0;
-                break;
-            }
-            part_buffer[part_len] = *c;
-            part_len += 1;
-        }
-        parse_page(shared_iter, &mut result, &mut part_buffer, &mut part_len);
+    let mut column = 1;
+    for field in second.split(|c| *c == b',') {
+        result.push(parse_bus(field, line, column)?);
+        column += field.len() + 1;
     }
-    if let Some(earliest_departure) = some_earliest_departure {
-        while let Ok(page_len) = reader.read(&mut page) {
-            if page_len == 0 {
-                break;
-            }
-            parse_page(
-                page[..page_len].iter(),
-                &mut result,
-                &mut part_buffer,
-                &mut part_len,
-            );
-        }
-        if part_len != 0 {
-            parse_part(&mut result, &part_buffer[..part_len]);
-        }
-        if result.len() != 0 {
-            return Some((earliest_departure, result));
+    if result.iter().all(Option::is_none) {
+        return Err(ParseError::syntax(
+            line,
+            1,
+            second,
+            "at least one bus number",
+        ));
+    }
+
+    while let Some((line, rest)) = lines.next_line()? {
+        if !rest.is_empty() {
+            return Err(ParseError::syntax(line, 1, rest, "end of input"));
         }
     }
 
-    None
+    Ok((earliest_departure, result))
 }
 
 pub fn p1_solve((start_at, bus_numbers): &(i64, Vec<Option<i64>>)) -> Option<i64> {
     let mut counters = HashMap::new();
-    for some_number in bus_numbers {
-        if let Some(number) = some_number {
-            counters.insert(*number, start_at % number);
-        }
+    for number in bus_numbers.iter().flatten() {
+        counters.insert(*number, start_at % number);
+    }
+    // without a bus nobody ever leaves
+    if counters.is_empty() {
+        return None;
     }
     let mut minute = 0;
     loop {
         for (bus_number, minutes) in counters.iter_mut() {
             if minutes == bus_number {
-                return Some(minute * (*bus_number) as i64);
+                return Some(minute * (*bus_number));
             }
             *minutes += 1;
         }
@@ -133,7 +107,7 @@ pub fn p2_solve((_, bus_numbers): &(i64, Vec<Option<i64>>)) -> Option<i64> {
             if first_number.is_none() {
                 first_number = Some(number);
             }
-            let modulo = *number as i64;
+            let modulo = *number;
             let mut remainder = modulo - (i as i64 % modulo);
             if remainder == modulo {
                 remainder = 0;
@@ -142,7 +116,7 @@ pub fn p2_solve((_, bus_numbers): &(i64, Vec<Option<i64>>)) -> Option<i64> {
         }
     }
     // linear congruence & chinese remainder
-    let n_all: i64 = expected.iter().map(|(v, _)| v).product();
+    let n_all: i64 = expected.keys().product();
     let mut sum = 0;
     for (modulo, residue) in expected.iter() {
         let n = n_all / modulo;
@@ -152,3 +126,22 @@ pub fn p2_solve((_, bus_numbers): &(i64, Vec<Option<i64>>)) -> Option<i64> {
     }
     Some(sum % n_all)
 }
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = (i64, Vec<Option<i64>>);
+    type Output = i64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
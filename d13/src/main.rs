@@ -1,6 +1,6 @@
 extern crate d13;
 use d13::{p1_solve, p2_solve, parse};
-use std::{env, io};
+use std::{env, io, process};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -12,16 +12,19 @@ fn main() {
         .parse()
         .expect("Part must be a number");
 
-    let some_parsed = parse(io::stdin().lock());
-    let mut result = None;
-
-    if let Some(parsed) = some_parsed {
-        result = match part {
-            1 => p1_solve(&parsed),
-            2 => p2_solve(&parsed),
-            _ => None,
+    let parsed = match parse(io::stdin().lock()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
         }
-    }
+    };
+
+    let result = match part {
+        1 => p1_solve(&parsed),
+        2 => p2_solve(&parsed),
+        _ => None,
+    };
 
     if let Some(result) = result {
         println!("{}", result);
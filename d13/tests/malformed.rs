@@ -0,0 +1,42 @@
+use d13::{p1_solve, parse};
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn rejects_bus_numbers_that_are_not_positive() {
+    assert_eq!(
+        error("0\n0\n"),
+        "invalid input: line 2, column 1: expected positive bus number, found \"0\""
+    );
+    assert_eq!(
+        error("939\n7,x,-7\n"),
+        "invalid input: line 2, column 5: expected positive bus number, found \"-7\""
+    );
+    assert_eq!(
+        error("939\n7,y\n"),
+        "invalid input: line 2, column 3: expected bus number or 'x', found \"y\""
+    );
+}
+
+#[test]
+fn requires_a_bus() {
+    assert_eq!(
+        error("939\nx,x\n"),
+        "invalid input: line 2, column 1: expected at least one bus number, found \"x,x\""
+    );
+    assert_eq!(p1_solve(&(939, vec![None])), None);
+}
+
+#[test]
+fn rejects_a_bad_timestamp_and_trailing_lines() {
+    assert_eq!(
+        error("soon\n7\n"),
+        "invalid input: line 1, column 1: expected timestamp, found \"soon\""
+    );
+    assert_eq!(
+        error("939\n7\n\n13\n"),
+        "invalid input: line 4, column 1: expected end of input, found \"13\""
+    );
+}
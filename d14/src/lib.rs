@@ -4,7 +4,8 @@ extern crate nom;
 extern crate rustc_hash;
 
 use btoi::btoi;
-use nom::character::{is_alphanumeric, is_digit};
+use common::{LineReader, ParseError, Solution};
+use nom::character::is_digit;
 use rustc_hash::FxHashMap;
 use std::fmt;
 use std::io::BufRead;
@@ -32,7 +33,7 @@ pub struct Bitmask {
 impl From<&[u8]> for Bitmask {
     fn from(buffer: &[u8]) -> Self {
         let mut ones = 0;
-        let mut zeros = u64::max_value();
+        let mut zeros = u64::MAX;
         let mut pos = buffer.len();
         for c in buffer.iter() {
             pos -= 1;
@@ -69,7 +70,7 @@ impl fmt::Display for Bitmask {
     }
 }
 
-fn get_floating_recursive(value: u64, bit: usize, map: u64, mut result: &mut Vec<u64>) {
+fn get_floating_recursive(value: u64, bit: usize, map: u64, result: &mut Vec<u64>) {
     let is_floating = (map >> bit) & 1 == 0;
     if bit == 0 {
         if is_floating {
@@ -81,11 +82,11 @@ fn get_floating_recursive(value: u64, bit: usize, map: u64, mut result: &mut Vec
         return;
     }
     if is_floating {
-        get_floating_recursive(value | 1 << bit, bit - 1, map, &mut result);
-        get_floating_recursive(value & !(1 << bit), bit - 1, map, &mut result);
+        get_floating_recursive(value | 1 << bit, bit - 1, map, result);
+        get_floating_recursive(value & !(1 << bit), bit - 1, map, result);
         return;
     }
-    get_floating_recursive(value, bit - 1, map, &mut result);
+    get_floating_recursive(value, bit - 1, map, result);
 }
 
 impl Bitmask {
@@ -97,7 +98,7 @@ impl Bitmask {
         let value = value | self.ones;
         let float_map = !self.zeros | self.ones;
 
-        let masked = float_map | u64::max_value() << 36;
+        let masked = float_map | u64::MAX << 36;
         let mut result = Vec::with_capacity(usize::pow(2, masked.count_zeros()));
         get_floating_recursive(value, 35, float_map, &mut result);
         result.into_boxed_slice()
@@ -116,73 +117,86 @@ impl fmt::Display for AddressValue {
     }
 }
 
+// a line as the parsers read it; the numbers of a write are left as digits,
+// so that parse_line can point at one that does not fit
+enum Line<'a> {
+    UpdateBitmask(Bitmask),
+    WriteValue { address: &'a [u8], value: &'a [u8] },
+}
+
 named!(
-    update_bitmask<Instruction>,
+    update_bitmask<Line>,
     do_parse!(
         tag!("mask = ")
-            >> bitmask: take_while!(is_alphanumeric)
+            >> bitmask: is_a!("X01")
             >> char!('\n')
-            >> (Instruction::UpdateBitmask(Bitmask::from(bitmask)))
+            >> (Line::UpdateBitmask(Bitmask::from(bitmask)))
     )
 );
 
 named!(
-    write_value<Instruction>,
+    write_value<Line>,
     do_parse!(
         tag!("mem")
-            >> address: delimited!(char!('['), take_while!(is_digit), char!(']'))
+            >> address: delimited!(char!('['), take_while1!(is_digit), char!(']'))
             >> tag!(" = ")
-            >> value: take_while!(is_digit)
+            >> value: take_while1!(is_digit)
             >> char!('\n')
-            >> (Instruction::WriteValue(AddressValue {
-                address: btoi(address).unwrap_or(0),
-                value: btoi(value).unwrap_or(0),
-            }))
+            >> (Line::WriteValue { address, value })
     )
 );
 
+// picks the parser from the first bytes rather than with alt!, which would only
+// report that neither of them matched at the start of the line
 named!(
-    read_instruction<Instruction>,
-    alt!(write_value | update_bitmask)
+    read_instruction<Line>,
+    switch!(peek!(take!(2)),
+        b"me" => call!(write_value)
+        | _ => call!(update_bitmask)
+    )
 );
 
-pub fn parse<R>(mut reader: R) -> Vec<Instruction>
+// expects the line to end with '\n', so that the streaming parsers know where
+// the last number stops
+fn parse_line(line: &[u8], line_no: usize) -> Result<Instruction, ParseError> {
+    let rest = match read_instruction(line) {
+        Ok((&[], Line::UpdateBitmask(bitmask))) => return Ok(Instruction::UpdateBitmask(bitmask)),
+        Ok((&[], Line::WriteValue { address, value })) => {
+            return Ok(Instruction::WriteValue(AddressValue {
+                address: btoi(address)
+                    .map_err(|_| ParseError::within(line_no, line, address, "address"))?,
+                value: btoi(value)
+                    .map_err(|_| ParseError::within(line_no, line, value, "value"))?,
+            }))
+        }
+        Ok((rest, _)) => rest,
+        Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => err.input,
+        Err(nom::Err::Incomplete(_)) => &line[line.len()..],
+    };
+    Err(ParseError::syntax(
+        line_no,
+        line.len() - rest.len() + 1,
+        rest.strip_suffix(b"\n").unwrap_or(rest),
+        "\"mask = <bitmask>\" or \"mem[<address>] = <value>\"",
+    ))
+}
+
+pub fn parse<R>(reader: R) -> Result<Vec<Instruction>, ParseError>
 where
     R: BufRead,
 {
     let mut result = Vec::new();
-    let mut page: [u8; 4096] = [0; 4096];
-    let mut line_buf: [u8; 1024] = [0; 1024];
-    let mut line_len = 0;
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        for c in page[..page_len].iter() {
-            match c {
-                b'\n' => {
-                    line_buf[line_len] = *c;
-                    line_len += 1;
-                    if let Ok((_, instruction)) = read_instruction(&line_buf[..line_len]) {
-                        result.push(instruction);
-                    }
-                    line_len = 0;
-                }
-                _ => {
-                    line_buf[line_len] = *c;
-                    line_len += 1;
-                }
-            }
-        }
-    }
-    if line_len > 0 {
-        line_buf[line_len] = b'\n';
-        line_len += 1;
-        if let Ok((_, instruction)) = read_instruction(&line_buf[..line_len]) {
-            result.push(instruction);
+    let mut line_buf = Vec::new();
+    let mut lines = LineReader::new(reader);
+    while let Some((line, text)) = lines.next_line()? {
+        if !text.is_empty() {
+            line_buf.clear();
+            line_buf.extend_from_slice(text);
+            line_buf.push(b'\n');
+            result.push(parse_line(&line_buf, line)?);
         }
     }
-    result
+    Ok(result)
 }
 
 pub fn p1_solve(instructions: &[Instruction]) -> Option<u64> {
@@ -205,7 +219,7 @@ pub fn p1_solve(instructions: &[Instruction]) -> Option<u64> {
             }
         }
     }
-    Some(values.iter().map(|(_, v)| v).sum())
+    Some(values.values().sum())
 }
 
 pub fn p2_solve(instructions: &[Instruction]) -> Option<u64> {
@@ -228,5 +242,24 @@ pub fn p2_solve(instructions: &[Instruction]) -> Option<u64> {
             }
         }
     }
-    Some(values.iter().map(|(_, v)| v).sum())
+    Some(values.values().sum())
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Instruction>;
+    type Output = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
 }
@@ -0,0 +1,58 @@
+use d14::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+const EXPECTED: &str = "expected \"mask = <bitmask>\" or \"mem[<address>] = <value>\"";
+
+#[test]
+fn points_at_numbers_that_do_not_fit() {
+    assert_eq!(
+        error("mask = XXX1\nmem[99999999999999999999] = 1\n"),
+        "invalid input: line 2, column 5: expected address, found \"99999999999999999999\""
+    );
+    assert_eq!(
+        error("mem[8] = 99999999999999999999\n"),
+        "invalid input: line 1, column 10: expected value, found \"99999999999999999999\""
+    );
+}
+
+#[test]
+fn points_at_where_a_line_stops_making_sense() {
+    assert_eq!(
+        error("mask = X01Y\n"),
+        format!(
+            "invalid input: line 1, column 11: {}, found \"Y\"",
+            EXPECTED
+        )
+    );
+    assert_eq!(
+        error("mask = \n"),
+        format!(
+            "invalid input: line 1, column 8: {}, found end of line",
+            EXPECTED
+        )
+    );
+    assert_eq!(
+        error("mem[8] = x\n"),
+        format!(
+            "invalid input: line 1, column 10: {}, found \"x\"",
+            EXPECTED
+        )
+    );
+    assert_eq!(
+        error("mem[8] = 11 \n"),
+        format!(
+            "invalid input: line 1, column 12: {}, found \" \"",
+            EXPECTED
+        )
+    );
+    assert_eq!(
+        error("mask = 1\nmen[8] = 1\n"),
+        format!(
+            "invalid input: line 2, column 1: {}, found \"men[8] = 1\"",
+            EXPECTED
+        )
+    );
+}
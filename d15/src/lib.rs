@@ -2,39 +2,26 @@ extern crate btoi;
 extern crate rustc_hash;
 
 use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
 use rustc_hash::FxHashMap;
 use std::io::BufRead;
 
-pub fn parse<R>(mut reader: R) -> (FxHashMap<usize, usize>, usize)
+pub fn parse<R>(reader: R) -> Result<(FxHashMap<usize, usize>, usize), ParseError>
 where
     R: BufRead,
 {
     let mut result = FxHashMap::default();
-    let mut buffer: [u8; 4096] = [0; 4096];
-    let mut num_buf: [u8; 16] = [0; 16];
-    let mut num_len = 0;
-    let mut turn = 0;
+    let mut lines = LineReader::new(reader);
+    let (line_no, line) = lines.next_line()?.unwrap_or((1, b""));
     let mut last = 0;
-    if let Ok(page_len) = reader.read(&mut buffer) {
-        for c in buffer[..page_len].iter() {
-            if c == &b',' {
-                if let Ok(int) = btoi::<usize>(&num_buf[..num_len]) {
-                    result.insert(int, turn);
-                    last = int;
-                    num_len = 0;
-                    turn += 1;
-                }
-            } else {
-                num_buf[num_len] = *c;
-                num_len += 1;
-            }
-        }
-        if let Ok(int) = btoi::<usize>(&num_buf[..num_len]) {
-            result.insert(int, turn);
-            last = int;
-        }
+    let mut column = 1;
+    for (turn, number) in line.split(|c| *c == b',').enumerate() {
+        last = btoi::<usize>(number)
+            .map_err(|_| ParseError::syntax(line_no, column, number, "starting number"))?;
+        result.insert(last, turn);
+        column += number.len() + 1;
     }
-    (result, last)
+    Ok((result, last))
 }
 
 pub fn p1_solve((history, last): &(FxHashMap<usize, usize>, usize)) -> Option<usize> {
@@ -43,7 +30,7 @@ pub fn p1_solve((history, last): &(FxHashMap<usize, usize>, usize)) -> Option<us
     for turn in (history.len() - 1)..(2020 - 1) {
         let turns_ago = history
             .get(&last)
-            .and_then(|prev_turn| Some(turn - prev_turn))
+            .map(|prev_turn| turn - prev_turn)
             .unwrap_or(0);
         history.insert(last, turn);
         last = turns_ago;
@@ -57,10 +44,29 @@ pub fn p2_solve((history, last): &(FxHashMap<usize, usize>, usize)) -> Option<us
     for turn in (history.len() - 1)..(30000000 - 1) {
         let turns_ago = history
             .get(&last)
-            .and_then(|prev_turn| Some(turn - prev_turn))
+            .map(|prev_turn| turn - prev_turn)
             .unwrap_or(0);
         history.insert(last, turn);
         last = turns_ago;
     }
     Some(last)
 }
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (FxHashMap<usize, usize>, usize);
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
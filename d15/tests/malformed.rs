@@ -0,0 +1,25 @@
+use d15::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn points_at_the_starting_number() {
+    assert_eq!(
+        error("0,3,x\n"),
+        "invalid input: line 1, column 5: expected starting number, found \"x\""
+    );
+    assert_eq!(
+        error("0,,6\n"),
+        "invalid input: line 1, column 3: expected starting number, found end of line"
+    );
+    assert_eq!(
+        error("0,3,6,\n"),
+        "invalid input: line 1, column 7: expected starting number, found end of line"
+    );
+    assert_eq!(
+        error(""),
+        "invalid input: line 1, column 1: expected starting number, found end of line"
+    );
+}
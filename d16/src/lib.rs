@@ -3,6 +3,7 @@ extern crate btoi;
 extern crate nom;
 
 use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
 use nom::character::{is_alphabetic, is_digit, is_space};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::hash::{Hash, Hasher};
@@ -53,92 +54,88 @@ pub struct Ticket {
     values: Box<[u16]>,
 }
 
+// the numbers of a rule or ticket are left as digits by the line parsers, so
+// that one that does not fit can be pointed at
+type Digits<'a> = &'a [u8];
+
 #[derive(Debug)]
-enum RulePart {
-    Result(Rule),
+enum RulePart<'a> {
+    Result {
+        name: String,
+        range_a: (Digits<'a>, Digits<'a>),
+        range_b: (Digits<'a>, Digits<'a>),
+    },
     Break,
 }
 
 named!(
-    parse_number_range<NumberRange>,
-    do_parse!(
-        min: take_while1!(is_digit)
-            >> char!('-')
-            >> max: take_while1!(is_digit)
-            >> (NumberRange {
-                min: btoi(min).unwrap_or(0),
-                max: btoi(max).unwrap_or(0)
-            })
-    )
+    parse_number_range<(Digits, Digits)>,
+    separated_pair!(take_while1!(is_digit), char!('-'), take_while1!(is_digit))
 );
 
 fn is_alphabetic_or_space(byte: u8) -> bool {
     is_alphabetic(byte) || is_space(byte)
 }
 
+// the line parsers pick their branch from the first byte rather than with
+// alt!, which would only report that none of them matched at the start
 named!(
     parse_rule<RulePart>,
-    alt!(
-        do_parse!(
+    switch!(peek!(take!(1)),
+        b"\n" => do_parse!(char!('\n') >> (RulePart::Break))
+        | _ => do_parse!(
             name: take_while1!(is_alphabetic_or_space)
                 >> tag!(": ")
                 >> range_a: parse_number_range
                 >> tag!(" or ")
                 >> range_b: parse_number_range
                 >> char!('\n')
-                >> (RulePart::Result(Rule {
+                >> (RulePart::Result {
                     name: String::from(from_utf8(name).unwrap_or("")),
                     range_a,
                     range_b
-                }))
-        ) | do_parse!(char!('\n') >> (RulePart::Break))
+                })
+        )
     )
 );
 
 #[derive(Debug)]
-enum MyTicketPart {
+enum MyTicketPart<'a> {
     Header,
-    Ticket(Ticket),
+    Ticket(Vec<Digits<'a>>),
     Break,
 }
 
 named!(
-    parse_ticket<Ticket>,
-    do_parse!(
-        values: separated_list1!(tag!(","), take_while1!(is_digit))
-            >> char!('\n')
-            >> (Ticket {
-                values: values
-                    .iter()
-                    .map(|v| btoi(v).unwrap_or(0))
-                    .collect::<Vec<u16>>()
-                    .into_boxed_slice()
-            })
+    parse_ticket<Vec<Digits>>,
+    terminated!(
+        separated_list1!(tag!(","), take_while1!(is_digit)),
+        char!('\n')
     )
 );
 
 named!(
     parse_my_ticket<MyTicketPart>,
-    alt!(
-        do_parse!(ticket: parse_ticket >> (MyTicketPart::Ticket(ticket)))
-            | do_parse!(tag!("your ticket:\n") >> (MyTicketPart::Header))
-            | do_parse!(char!('\n') >> (MyTicketPart::Break))
+    switch!(peek!(take!(1)),
+        b"\n" => do_parse!(char!('\n') >> (MyTicketPart::Break))
+        | b"y" => do_parse!(tag!("your ticket:\n") >> (MyTicketPart::Header))
+        | _ => do_parse!(ticket: parse_ticket >> (MyTicketPart::Ticket(ticket)))
     )
 );
 
 #[derive(Debug)]
-enum NearbyTicketsPart {
+enum NearbyTicketsPart<'a> {
     Header,
-    Ticket(Ticket),
+    Ticket(Vec<Digits<'a>>),
     Break,
 }
 
 named!(
     parse_nearby_tickets<NearbyTicketsPart>,
-    alt!(
-        do_parse!(ticket: parse_ticket >> (NearbyTicketsPart::Ticket(ticket)))
-            | do_parse!(tag!("nearby tickets:\n") >> (NearbyTicketsPart::Header))
-            | do_parse!(char!('\n') >> (NearbyTicketsPart::Break))
+    switch!(peek!(take!(1)),
+        b"\n" => do_parse!(char!('\n') >> (NearbyTicketsPart::Break))
+        | b"n" => do_parse!(tag!("nearby tickets:\n") >> (NearbyTicketsPart::Header))
+        | _ => do_parse!(ticket: parse_ticket >> (NearbyTicketsPart::Ticket(ticket)))
     )
 );
 
@@ -150,11 +147,57 @@ enum ParserState {
     Done,
 }
 
+// turns the result of one of the line parsers into a syntax error pointing at
+// where it gave up; lines still end with '\n' at this point
+fn complete<T>(
+    line: &[u8],
+    line_no: usize,
+    result: nom::IResult<&[u8], T>,
+    expected: &'static str,
+) -> Result<T, ParseError> {
+    let rest = match result {
+        Ok((&[], value)) => return Ok(value),
+        Ok((rest, _)) => rest,
+        Err(nom::Err::Error(err)) | Err(nom::Err::Failure(err)) => err.input,
+        Err(nom::Err::Incomplete(_)) => &line[line.len()..],
+    };
+    Err(ParseError::syntax(
+        line_no,
+        line.len() - rest.len() + 1,
+        rest.strip_suffix(b"\n").unwrap_or(rest),
+        expected,
+    ))
+}
+
+fn number(line: &[u8], line_no: usize, digits: Digits) -> Result<u16, ParseError> {
+    btoi(digits).map_err(|_| ParseError::within(line_no, line, digits, "number from 0 to 65535"))
+}
+
+fn number_range(
+    line: &[u8],
+    line_no: usize,
+    (min, max): (Digits, Digits),
+) -> Result<NumberRange, ParseError> {
+    Ok(NumberRange {
+        min: number(line, line_no, min)?,
+        max: number(line, line_no, max)?,
+    })
+}
+
+fn ticket(line: &[u8], line_no: usize, values: Vec<Digits>) -> Result<Ticket, ParseError> {
+    Ok(Ticket {
+        values: values
+            .into_iter()
+            .map(|value| number(line, line_no, value))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
 #[derive(Debug)]
 pub struct Parser {
     state: ParserState,
-    buffer: [u8; 1024],
-    length: usize,
+    buffer: Vec<u8>,
+    line: usize,
 
     result_rules: Vec<Rule>,
     result_my_ticket: Option<Ticket>,
@@ -165,78 +208,111 @@ impl Parser {
     fn new() -> Self {
         Self {
             state: ParserState::RulePart,
-            buffer: [0; 1024],
-            length: 0,
+            buffer: Vec::new(),
+            line: 1,
             result_rules: Vec::new(),
             result_my_ticket: None,
             result_nearby_tickets: Vec::new(),
         }
     }
 
-    fn parse(&mut self, input: std::slice::Iter<u8>) {
-        for c in input {
-            self.buffer[self.length] = *c;
-            self.length += 1;
-            if c == &b'\n' {
-                self.parse_line();
-                self.length = 0;
-            }
-        }
+    // the line parsers are streaming, so they get to see the line ending
+    fn parse(&mut self, line: &[u8]) -> Result<(), ParseError> {
+        self.buffer.clear();
+        self.buffer.extend_from_slice(line);
+        self.buffer.push(b'\n');
+        self.parse_line()?;
+        self.line += 1;
+        Ok(())
     }
 
-    fn parse_line(&mut self) {
+    fn parse_line(&mut self) -> Result<(), ParseError> {
+        let line = self.buffer.as_slice();
         match self.state {
             ParserState::RulePart => {
-                if let Ok((_, result)) = parse_rule(&self.buffer[..self.length]) {
-                    match result {
-                        RulePart::Break => self.state = ParserState::MyTicketPart,
-                        RulePart::Result(rule) => self.result_rules.push(rule),
-                    }
+                match complete(
+                    line,
+                    self.line,
+                    parse_rule(line),
+                    "\"<field>: <range> or <range>\"",
+                )? {
+                    RulePart::Break => self.state = ParserState::MyTicketPart,
+                    RulePart::Result {
+                        name,
+                        range_a,
+                        range_b,
+                    } => self.result_rules.push(Rule {
+                        name,
+                        range_a: number_range(line, self.line, range_a)?,
+                        range_b: number_range(line, self.line, range_b)?,
+                    }),
                 }
             }
             ParserState::MyTicketPart => {
-                if let Ok((_, result)) = parse_my_ticket(&self.buffer[..self.length]) {
-                    match result {
-                        MyTicketPart::Break => self.state = ParserState::NearbyTicketsPart,
-                        MyTicketPart::Header => (),
-                        MyTicketPart::Ticket(ticket) => self.result_my_ticket = Some(ticket),
+                match complete(
+                    line,
+                    self.line,
+                    parse_my_ticket(line),
+                    "\"your ticket:\" or ticket values",
+                )? {
+                    MyTicketPart::Break => self.state = ParserState::NearbyTicketsPart,
+                    MyTicketPart::Header => (),
+                    MyTicketPart::Ticket(values) => {
+                        self.result_my_ticket = Some(ticket(line, self.line, values)?)
                     }
                 }
             }
             ParserState::NearbyTicketsPart => {
-                if let Ok((_, result)) = parse_nearby_tickets(&self.buffer[..self.length]) {
-                    match result {
-                        NearbyTicketsPart::Break => self.state = ParserState::Done,
-                        NearbyTicketsPart::Header => (),
-                        NearbyTicketsPart::Ticket(ticket) => {
-                            self.result_nearby_tickets.push(ticket)
-                        }
-                    }
+                match complete(
+                    line,
+                    self.line,
+                    parse_nearby_tickets(line),
+                    "\"nearby tickets:\" or ticket values",
+                )? {
+                    NearbyTicketsPart::Break => self.state = ParserState::Done,
+                    NearbyTicketsPart::Header => (),
+                    NearbyTicketsPart::Ticket(values) => self
+                        .result_nearby_tickets
+                        .push(ticket(line, self.line, values)?),
+                }
+            }
+            ParserState::Done => {
+                if line != b"\n" {
+                    return Err(ParseError::syntax(
+                        self.line,
+                        1,
+                        line.strip_suffix(b"\n").unwrap_or(line),
+                        "end of input",
+                    ));
                 }
             }
-            ParserState::Done => {}
         }
+        Ok(())
     }
 }
 
-pub fn parse<R>(mut reader: R) -> (Box<[Rule]>, Ticket, Box<[Ticket]>)
+type ParseResult = (Box<[Rule]>, Ticket, Box<[Ticket]>);
+
+pub fn parse<R>(reader: R) -> Result<ParseResult, ParseError>
 where
     R: BufRead,
 {
-    let mut page: [u8; 4096] = [0; 4096];
+    let mut lines = LineReader::new(reader);
     let mut parser = Parser::new();
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        parser.parse(page[..page_len].iter());
+    while let Some((_, line)) = lines.next_line()? {
+        parser.parse(line)?;
     }
-    parser.parse(b"\n".iter());
-    (
+    // a blank line closes the last section
+    let line = parser.line;
+    parser.parse(b"")?;
+    let my_ticket = parser
+        .result_my_ticket
+        .ok_or_else(|| ParseError::syntax(line, 1, b"", "\"your ticket:\" section"))?;
+    Ok((
         parser.result_rules.into_boxed_slice(),
-        parser.result_my_ticket.expect("my ticket"),
+        my_ticket,
         parser.result_nearby_tickets.into_boxed_slice(),
-    )
+    ))
 }
 
 pub fn p1_solve((rules, _, nearby_tickets): &(Box<[Rule]>, Ticket, Box<[Ticket]>)) -> Option<u64> {
@@ -295,7 +371,7 @@ pub fn p2_solve(
                     )
                 })
                 .collect::<FxHashMap<&Rule, usize>>();
-            let sum: usize = invalid_per_rule.iter().map(|(_, v)| v).sum();
+            let sum: usize = invalid_per_rule.values().sum();
             if (invalid_per_rule.len() - sum) == 1 {
                 for (rule, count) in invalid_per_rule {
                     if count == 0 {
@@ -308,7 +384,7 @@ pub fn p2_solve(
         }
         for (rule, field) in mappings.iter() {
             remaining_rules.remove(rule);
-            values_by_field.remove(&field);
+            values_by_field.remove(field);
         }
         if changes == 0 {
             break;
@@ -325,3 +401,22 @@ pub fn p2_solve(
 
     Some(result)
 }
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = (Box<[Rule]>, Ticket, Box<[Ticket]>);
+    type Output = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
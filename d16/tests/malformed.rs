@@ -0,0 +1,55 @@
+use d16::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+const RULES: &str = "class: 1-3 or 5-7\nrow: 6-11 or 33-44\n\n";
+const MINE: &str = "your ticket:\n7,1,14\n\n";
+
+#[test]
+fn points_at_numbers_that_do_not_fit() {
+    assert_eq!(
+        error("class: 1-70000 or 5-7\n"),
+        "invalid input: line 1, column 10: expected number from 0 to 65535, found \"70000\""
+    );
+    assert_eq!(
+        error(&format!("{}{}nearby tickets:\n7,3,70000\n", RULES, MINE)),
+        "invalid input: line 8, column 5: expected number from 0 to 65535, found \"70000\""
+    );
+}
+
+#[test]
+fn points_at_where_a_line_stops_making_sense() {
+    assert_eq!(
+        error("class: 1-3 5-7\n"),
+        "invalid input: line 1, column 11: expected \"<field>: <range> or <range>\", found \" 5-7\""
+    );
+    assert_eq!(
+        error("class 1-3 or 5-7\n"),
+        "invalid input: line 1, column 7: expected \"<field>: <range> or <range>\", found \"1-3 or 5-7\""
+    );
+    assert_eq!(
+        error(&format!("{}your ticket:\n7,1,x\n", RULES)),
+        "invalid input: line 5, column 4: expected \"your ticket:\" or ticket values, found \",x\""
+    );
+    assert_eq!(
+        error(&format!("{}{}nearby ticket:\n", RULES, MINE)),
+        "invalid input: line 7, column 1: expected \"nearby tickets:\" or ticket values, found \"nearby ticket:\""
+    );
+}
+
+#[test]
+fn needs_my_ticket_and_nothing_after_the_nearby_ones() {
+    assert_eq!(
+        error(RULES),
+        "invalid input: line 4, column 1: expected \"your ticket:\" section, found end of line"
+    );
+    assert_eq!(
+        error(&format!(
+            "{}{}nearby tickets:\n7,3,47\n\nmore\n",
+            RULES, MINE
+        )),
+        "invalid input: line 10, column 1: expected end of input, found \"more\""
+    );
+}
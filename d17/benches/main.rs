@@ -1,15 +1,9 @@
+use common::bench::bench;
 use criterion::{criterion_group, criterion_main, Criterion};
-use d17::{p1_solve, p2_solve, parse};
-use std::io::{stdin, Read};
+use d17::Day17;
 
 fn main_benchmark(c: &mut Criterion) {
-    let mut buffer = String::new();
-    if stdin().lock().read_to_string(&mut buffer).is_ok() {
-        c.bench_function("d17 parse", |b| b.iter(|| parse(buffer.as_bytes())));
-        let parsed = parse(buffer.as_bytes());
-        c.bench_function("d17 p1 (391)", |b| b.iter(|| p1_solve(&parsed)));
-        c.bench_function("d17 p2 (2264)", |b| b.iter(|| p2_solve(&parsed)));
-    }
+    bench::<Day17>(c, env!("CARGO_MANIFEST_DIR"));
 }
 
 criterion_group!(benches, main_benchmark);
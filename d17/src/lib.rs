@@ -1,7 +1,8 @@
 extern crate rustc_hash;
+use common::{LineReader, ParseError, Solution};
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::fmt;
 use std::io::BufRead;
-use rustc_hash::{FxHashMap, FxHashSet};
 
 #[derive(Eq, Hash, PartialEq)]
 pub struct Coordinate<T> {
@@ -45,7 +46,7 @@ where
     T: Copy,
 {
     fn clone(&self) -> Self {
-        Self { value: self.value }
+        *self
     }
 }
 
@@ -131,32 +132,40 @@ impl Iterator for Neighbors<[i32; 4]> {
     }
 }
 
-pub fn parse<R>(mut reader: R) -> (Box<[bool]>, usize)
+pub fn parse<R>(reader: R) -> Result<(Box<[bool]>, usize), ParseError>
 where
     R: BufRead,
 {
     let mut result = Vec::new();
     let mut width = None;
-    let mut page: [u8; 4096] = [0; 4096];
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        result.reserve(page_len);
-        for c in page[..page_len].iter() {
-            match *c {
-                b'\n' => {
-                    if width.is_none() {
-                        width = Some(result.len());
-                    }
+    let mut last_line = 0;
+    let mut lines = LineReader::new(reader);
+    while let Some((line, row)) = lines.next_line()? {
+        last_line = line;
+        if row.is_empty() {
+            continue;
+        }
+        for (i, c) in row.iter().enumerate() {
+            match c {
+                _ if width.is_some_and(|width| i >= width) => {
+                    return Err(ParseError::syntax(line, i + 1, &[*c], "end of row"));
                 }
                 b'.' => result.push(false),
                 b'#' => result.push(true),
-                _ => (),
+                _ => return Err(ParseError::syntax(line, i + 1, &[*c], "'#' or '.'")),
             }
         }
+        match width {
+            Some(width) if row.len() < width => {
+                return Err(ParseError::syntax(line, row.len() + 1, b"", "'#' or '.'"));
+            }
+            Some(_) => (),
+            None => width = Some(row.len()),
+        }
     }
-    (result.into_boxed_slice(), width.expect("width"))
+    // a grid needs at least one row to have a width
+    let width = width.ok_or_else(|| ParseError::syntax(last_line + 1, 1, b"", "'#' or '.'"))?;
+    Ok((result.into_boxed_slice(), width))
 }
 
 pub fn p1_solve((result, width): &(Box<[bool]>, usize)) -> Option<usize> {
@@ -187,13 +196,13 @@ pub fn p1_solve((result, width): &(Box<[bool]>, usize)) -> Option<usize> {
             match count {
                 2 => (),
                 3 => {
-                    if !active.contains(&coordinate) {
+                    if !active.contains(coordinate) {
                         active.insert(*coordinate);
                     }
                 }
                 _ => {
-                    if active.contains(&coordinate) {
-                        active.remove(&coordinate);
+                    if active.contains(coordinate) {
+                        active.remove(coordinate);
                     }
                 }
             }
@@ -232,13 +241,13 @@ pub fn p2_solve((result, width): &(Box<[bool]>, usize)) -> Option<usize> {
             match count {
                 2 => (),
                 3 => {
-                    if !active.contains(&coordinate) {
+                    if !active.contains(coordinate) {
                         active.insert(*coordinate);
                     }
                 }
                 _ => {
-                    if active.contains(&coordinate) {
-                        active.remove(&coordinate);
+                    if active.contains(coordinate) {
+                        active.remove(coordinate);
                     }
                 }
             }
@@ -247,3 +256,22 @@ pub fn p2_solve((result, width): &(Box<[bool]>, usize)) -> Option<usize> {
     }
     Some(active.len())
 }
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = (Box<[bool]>, usize);
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
@@ -0,0 +1,25 @@
+use d17::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn rejects_ragged_rows_and_unknown_cells() {
+    assert_eq!(
+        error(".#.\n..#.\n"),
+        "invalid input: line 2, column 4: expected end of row, found \".\""
+    );
+    assert_eq!(
+        error(".#.\n\n..\n"),
+        "invalid input: line 3, column 3: expected '#' or '.', found end of line"
+    );
+    assert_eq!(
+        error(".#.\n.L#\n"),
+        "invalid input: line 2, column 2: expected '#' or '.', found \"L\""
+    );
+    assert_eq!(
+        error("\n"),
+        "invalid input: line 2, column 1: expected '#' or '.', found end of line"
+    );
+}
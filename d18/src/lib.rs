@@ -1,6 +1,7 @@
 extern crate btoi;
 
 use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
 use std::io::BufRead;
 
 #[derive(Debug)]
@@ -13,47 +14,97 @@ pub enum Token {
     EndOfLine,
 }
 
-pub fn parse<R>(mut reader: R) -> Box<[Token]>
-where
-    R: BufRead,
-{
-    let mut result = Vec::new();
-    let mut page: [u8; 4096] = [0; 4096];
-    let mut line_buf: [u8; 1024] = [0; 1024];
-    let mut line_len = 0;
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        for c in page[..page_len].iter() {
-            match &c {
-                b'0' | b'1' | b'2' | b'3' | b'4' | b'5' | b'6' | b'7' | b'8' | b'9' => {
-                    line_buf[line_len] = *c;
-                    line_len += 1;
-                }
-                _ => {
-                    if line_len > 0 {
-                        result.push(Token::Number(btoi(&line_buf[..line_len]).unwrap_or(0)));
-                        line_len = 0;
-                    }
+// operands and operators have to take turns: a line starts with an operand,
+// `(` opens a new operand and `)` has to be followed by an operator
+fn tokenize_line(text: &[u8], line: usize, result: &mut Vec<Token>) -> Result<(), ParseError> {
+    let unexpected = |i: usize, found: &[u8]| {
+        Err(ParseError::syntax(
+            line,
+            i + 1,
+            found,
+            "operator or operand",
+        ))
+    };
+    // open parentheses so far
+    let mut depth = 0;
+    // where the number being read started
+    let mut number_start = None;
+    // whether an operand should come next, rather than an operator
+    let mut operand = true;
+    for (i, c) in text.iter().enumerate() {
+        match (c, number_start) {
+            (b'0'..=b'9', None) => number_start = Some(i),
+            (b'0'..=b'9', Some(_)) => (),
+            (_, Some(start)) => {
+                if !operand {
+                    return unexpected(start, &text[start..i]);
                 }
+                result.push(parse_number(&text[start..i], line, start)?);
+                number_start = None;
+                operand = false;
             }
+            (_, None) => (),
+        }
 
-            match &c {
-                b'\n' => result.push(Token::EndOfLine),
-                b'+' => result.push(Token::Add),
-                b'*' => result.push(Token::Multiply),
-                b'(' => result.push(Token::ParenthesesOpen),
-                b')' => result.push(Token::ParenthesesClose),
-                _ => (),
+        match c {
+            b'+' | b'*' if operand => return unexpected(i, &[*c]),
+            b'+' => {
+                result.push(Token::Add);
+                operand = true;
             }
+            b'*' => {
+                result.push(Token::Multiply);
+                operand = true;
+            }
+            b'(' if !operand => return unexpected(i, &[*c]),
+            b'(' => {
+                depth += 1;
+                result.push(Token::ParenthesesOpen);
+            }
+            b')' if depth == 0 || operand => return unexpected(i, &[*c]),
+            b')' => {
+                depth -= 1;
+                result.push(Token::ParenthesesClose);
+            }
+            b' ' | b'0'..=b'9' => (),
+            _ => return unexpected(i, &[*c]),
+        }
+    }
+    if let Some(start) = number_start {
+        if !operand {
+            return unexpected(start, &text[start..]);
         }
+        result.push(parse_number(&text[start..], line, start)?);
+        operand = false;
+    }
+    // a blank line has nothing to add up, and is left as it was
+    let blank = text.iter().all(|c| *c == b' ');
+    if operand && !blank {
+        return unexpected(text.len(), b"");
     }
-    if line_len > 0 {
-        result.push(Token::Number(btoi(&line_buf[..line_len]).unwrap_or(0)));
+    if depth > 0 {
+        return Err(ParseError::syntax(line, text.len() + 1, b"", "')'"));
     }
     result.push(Token::EndOfLine);
-    result.into_boxed_slice()
+    Ok(())
+}
+
+fn parse_number(number: &[u8], line: usize, start: usize) -> Result<Token, ParseError> {
+    btoi(number)
+        .map(Token::Number)
+        .map_err(|_| ParseError::syntax(line, start + 1, number, "number"))
+}
+
+pub fn parse<R>(reader: R) -> Result<Box<[Token]>, ParseError>
+where
+    R: BufRead,
+{
+    let mut result = Vec::new();
+    let mut lines = LineReader::new(reader);
+    while let Some((line, text)) = lines.next_line()? {
+        tokenize_line(text, line, &mut result)?;
+    }
+    Ok(result.into_boxed_slice())
 }
 
 #[derive(Debug)]
@@ -210,3 +261,22 @@ pub fn p2_solve(tokens: &[Token]) -> Option<i64> {
 
     Some(sum)
 }
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Box<[Token]>;
+    type Output = i64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
@@ -0,0 +1,45 @@
+use d18::{p1_solve, parse};
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn operands_and_operators_take_turns() {
+    for (input, column, found) in &[
+        ("1 2", 3, "\"2\""),
+        ("+", 1, "\"+\""),
+        ("1+*2", 3, "\"*\""),
+        ("(1+)", 4, "\")\""),
+        ("2*", 3, "end of line"),
+        ("()", 2, "\")\""),
+        ("1 (2)", 3, "\"(\""),
+        ("(1)2", 4, "\"2\""),
+        ("1)", 2, "\")\""),
+        ("1 & 2", 3, "\"&\""),
+    ] {
+        assert_eq!(
+            error(&format!("1 + 1\n{}\n", input)),
+            format!(
+                "invalid input: line 2, column {}: expected operator or operand, found {}",
+                column, found
+            ),
+            "{:?}",
+            input
+        );
+    }
+}
+
+#[test]
+fn parentheses_have_to_close() {
+    assert_eq!(
+        error("(1 + (2 * 3)\n"),
+        "invalid input: line 1, column 13: expected ')', found end of line"
+    );
+}
+
+#[test]
+fn well_formed_lines_still_parse() {
+    let tokens = parse("((2)) * 3\n\n1 + (2 * 3)\n".as_bytes()).unwrap();
+    assert_eq!(p1_solve(&tokens), Some(6 + 7));
+}
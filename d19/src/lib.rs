@@ -1,6 +1,7 @@
 extern crate btoi;
 extern crate rustc_hash;
 use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::io::BufRead;
 
@@ -127,17 +128,15 @@ pub struct BinaryRuleSequence {
     right: Box<[usize]>,
 }
 
-#[derive(Debug)]
-pub struct ConcreteRule {
-    value: u8,
-}
-
 pub struct RuleParser {
     state: RuleParserState,
     rules: Vec<RuleDef>,
     lhs: Vec<usize>,
     rhs: Vec<usize>,
     identifier: usize,
+    // every rule number used in a sequence, with where it is in the input, so
+    // that ones that are never defined can be pointed at
+    references: Vec<(usize, usize, usize)>,
 }
 
 #[derive(Debug)]
@@ -155,34 +154,48 @@ impl RuleParser {
             identifier: 0,
             lhs: Vec::new(),
             rhs: Vec::new(),
+            references: Vec::new(),
         }
     }
 
-    fn parse(&mut self, input: &[u8], token: &Token) -> bool {
+    // `input` is the whole puzzle input, `pos` where the token starts in it
+    fn parse(&mut self, input: &[u8], pos: usize, token: &Token) -> Result<bool, ParseError> {
+        let value = &input[pos..pos + token.len];
+        let number =
+            || btoi(value).map_err(|_| ParseError::at_offset(input, pos, token.len, "rule number"));
         match self.state {
             RuleParserState::Label => match token.kind {
                 TokenKind::Literal(LiteralKind::Number) => {
-                    self.identifier = btoi(input).unwrap();
+                    self.identifier = number()?;
                 }
                 TokenKind::Colon => {
                     self.state = RuleParserState::Lhs;
                 }
                 TokenKind::EndOfLine => {
-                    return false;
+                    return Ok(false);
                 }
                 TokenKind::Whitespace => (),
-                _ => panic!("unexpected format"),
+                _ => {
+                    return Err(ParseError::at_offset(
+                        input,
+                        pos,
+                        token.len,
+                        "rule number and ':'",
+                    ));
+                }
             },
             RuleParserState::Lhs => match token.kind {
                 TokenKind::Literal(LiteralKind::Number) => {
-                    self.lhs.push(btoi(input).unwrap());
+                    let rule = number()?;
+                    self.lhs.push(rule);
+                    self.references.push((rule, pos, token.len));
                 }
                 TokenKind::Pipe => {
                     self.state = RuleParserState::Rhs;
                 }
                 TokenKind::Literal(LiteralKind::Char) => {
                     self.rules
-                        .push(RuleDef::concrete(self.identifier, input[0]));
+                        .push(RuleDef::concrete(self.identifier, value[0]));
                     self.identifier = 0;
                 }
                 TokenKind::EndOfLine => {
@@ -197,15 +210,24 @@ impl RuleParser {
                     if token.len == 1 {
                         self.state = RuleParserState::Label;
                     } else {
-                        return false;
+                        return Ok(false);
                     }
                 }
                 TokenKind::Whitespace => (),
-                _ => panic!("unexpected format"),
+                _ => {
+                    return Err(ParseError::at_offset(
+                        input,
+                        pos,
+                        token.len,
+                        "rule number, '|' or character",
+                    ));
+                }
             },
             RuleParserState::Rhs => match token.kind {
                 TokenKind::Literal(LiteralKind::Number) => {
-                    self.rhs.push(btoi(input).unwrap());
+                    let rule = number()?;
+                    self.rhs.push(rule);
+                    self.references.push((rule, pos, token.len));
                 }
                 TokenKind::EndOfLine => {
                     self.rules.push(RuleDef::binary(
@@ -218,33 +240,28 @@ impl RuleParser {
                     if token.len == 1 {
                         self.state = RuleParserState::Label;
                     } else {
-                        return false;
+                        return Ok(false);
                     }
                 }
                 TokenKind::Whitespace => (),
-                _ => panic!("unexpected format"),
+                _ => {
+                    return Err(ParseError::at_offset(input, pos, token.len, "rule number"));
+                }
             },
         }
-        true
+        Ok(true)
     }
 }
 
 type ParseResult = (Box<[RuleDef]>, Box<[Box<[u8]>]>);
 
-pub fn parse<R>(mut reader: R) -> ParseResult
+pub fn parse<R>(reader: R) -> Result<ParseResult, ParseError>
 where
     R: BufRead,
 {
-    let mut page: [u8; 4096] = [0; 4096];
-    let mut input = Vec::new();
+    let input = LineReader::new(reader).read_to_end()?;
     let mut tokenizer = Tokenizer::new();
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        tokenizer.tokenize(&mut page[..page_len].iter());
-        input.extend_from_slice(&page[..page_len]);
-    }
+    tokenizer.tokenize(&mut input.iter());
     tokenizer.tokenize(&mut b"\n".iter());
 
     let mut rule_parser = RuleParser::new();
@@ -252,26 +269,45 @@ where
     let mut pos = 0;
     let mut iter = tokenizer.tokens.iter();
     for token in &mut iter {
-        if rule_parser.parse(&input[pos..pos + token.len], token) {
-            pos += token.len;
-        } else {
-            pos += token.len;
+        let more = rule_parser.parse(&input, pos, token)?;
+        pos += token.len;
+        if !more {
             break;
         }
     }
 
     let mut messages = Vec::new();
     for token in &mut iter {
-        if let TokenKind::Literal(LiteralKind::Char) = token.kind {
-            messages.push(Box::<[u8]>::from(&input[pos..pos + token.len]));
+        match token.kind {
+            TokenKind::Literal(LiteralKind::Char) => {
+                messages.push(Box::<[u8]>::from(&input[pos..pos + token.len]));
+            }
+            TokenKind::EndOfLine => (),
+            _ => {
+                return Err(ParseError::at_offset(&input, pos, token.len, "'a' or 'b'"));
+            }
         }
         pos += token.len;
     }
 
-    (
+    let defined: FxHashSet<usize> = rule_parser.rules.iter().map(|rule| rule.id).collect();
+    if let Some((_, pos, len)) = rule_parser
+        .references
+        .iter()
+        .find(|(rule, _, _)| !defined.contains(rule))
+    {
+        return Err(ParseError::at_offset(
+            &input,
+            *pos,
+            *len,
+            "defined rule number",
+        ));
+    }
+
+    Ok((
         rule_parser.rules.into_boxed_slice(),
         messages.into_boxed_slice(),
-    )
+    ))
 }
 
 fn p1_validate(message: &[u8], rule_map: &FxHashMap<usize, &RuleKind>, rule: &RuleKind) -> usize {
@@ -291,7 +327,7 @@ fn p1_validate(message: &[u8], rule_map: &FxHashMap<usize, &RuleKind>, rule: &Ru
             }
 
             for rule in rules.iter() {
-                let valid = p1_validate(&message[pos..], rule_map, &rule_map[&rule]);
+                let valid = p1_validate(&message[pos..], rule_map, rule_map[rule]);
                 if valid == 0 {
                     return 0;
                 } else {
@@ -306,7 +342,7 @@ fn p1_validate(message: &[u8], rule_map: &FxHashMap<usize, &RuleKind>, rule: &Ru
 
             if message.len() >= left.len() {
                 for rule in left.iter() {
-                    let valid = p1_validate(&message[pos..], rule_map, &rule_map[&rule]);
+                    let valid = p1_validate(&message[pos..], rule_map, rule_map[rule]);
                     if valid == 0 {
                         pos = 0;
                         break;
@@ -321,7 +357,7 @@ fn p1_validate(message: &[u8], rule_map: &FxHashMap<usize, &RuleKind>, rule: &Ru
             }
 
             for rule in right.iter() {
-                let valid = p1_validate(&message[pos..], rule_map, &rule_map[&rule]);
+                let valid = p1_validate(&message[pos..], rule_map, rule_map[rule]);
                 if valid == 0 {
                     return 0;
                 } else {
@@ -339,10 +375,11 @@ pub fn p1_solve((rule_defs, messages): &ParseResult) -> Option<usize> {
     for rule_def in rule_defs.iter() {
         map.insert(rule_def.id, &rule_def.rule);
     }
+    let rule_0 = *map.get(&0)?;
     Some(
         messages
             .iter()
-            .filter(|message| p1_validate(message, &map, map[&0]) == message.len())
+            .filter(|message| p1_validate(message, &map, rule_0) == message.len())
             .count(),
     )
 }
@@ -368,7 +405,7 @@ fn p2_validate(
                     .iter()
                     .cloned()
                     .flat_map(|p| {
-                        p2_validate(&message[p..], rule_map, &rule_map[&rule])
+                        p2_validate(&message[p..], rule_map, rule_map[rule])
                             .iter()
                             .map(move |v| v + p)
                             .collect::<FxHashSet<usize>>()
@@ -386,7 +423,7 @@ fn p2_validate(
                     .iter()
                     .cloned()
                     .flat_map(|p| {
-                        p2_validate(&message[p..], rule_map, &rule_map[&rule])
+                        p2_validate(&message[p..], rule_map, rule_map[rule])
                             .iter()
                             .map(move |v| v + p)
                             .collect::<FxHashSet<usize>>()
@@ -403,7 +440,7 @@ fn p2_validate(
                     .iter()
                     .cloned()
                     .flat_map(|p| {
-                        p2_validate(&message[p..], rule_map, &rule_map[&rule])
+                        p2_validate(&message[p..], rule_map, rule_map[rule])
                             .iter()
                             .map(move |v| v + p)
                             .collect::<FxHashSet<usize>>()
@@ -435,11 +472,36 @@ pub fn p2_solve((rule_defs, messages): &ParseResult) -> Option<usize> {
             _ => map.insert(rule_def.id, &rule_def.rule),
         };
     }
+    // the replacements for 8 and 11 lean on 42, and the one for 11 on 31
+    let rule_0 = *map.get(&0)?;
+    let defined = |rule| map.contains_key(&rule);
+    if ((defined(8) || defined(11)) && !defined(42)) || (defined(11) && !defined(31)) {
+        return None;
+    }
 
     Some(
         messages
             .iter()
-            .filter(|message| p2_validate(message, &map, map[&0]).contains(&message.len()))
+            .filter(|message| p2_validate(message, &map, rule_0).contains(&message.len()))
             .count(),
     )
 }
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = ParseResult;
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
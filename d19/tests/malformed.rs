@@ -0,0 +1,44 @@
+use d19::{p1_solve, p2_solve, parse};
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn rules_have_to_be_defined() {
+    assert_eq!(
+        error("0: 5\n\na\n"),
+        "invalid input: line 1, column 4: expected defined rule number, found \"5\""
+    );
+    assert_eq!(
+        error("0: 1 | 1 7\n1: \"a\"\n\na\n"),
+        "invalid input: line 1, column 10: expected defined rule number, found \"7\""
+    );
+}
+
+#[test]
+fn without_rule_0_nothing_matches() {
+    let parsed = parse("1: \"a\"\n\na\n".as_bytes()).unwrap();
+    assert_eq!(p1_solve(&parsed), None);
+    assert_eq!(p2_solve(&parsed), None);
+}
+
+#[test]
+fn rejects_malformed_rules_and_messages() {
+    assert_eq!(
+        error("a: 1\n"),
+        "invalid input: line 1, column 1: expected rule number and ':', found \"a\""
+    );
+    assert_eq!(
+        error("0: 1 : 2\n"),
+        "invalid input: line 1, column 6: expected rule number, '|' or character, found \":\""
+    );
+    assert_eq!(
+        error("0: 1 | 2 | 3\n"),
+        "invalid input: line 1, column 10: expected rule number, found \"|\""
+    );
+    assert_eq!(
+        error("0: \"a\"\n\nab\n12\n"),
+        "invalid input: line 4, column 1: expected 'a' or 'b', found \"12\""
+    );
+}
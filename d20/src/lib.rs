@@ -0,0 +1,354 @@
+extern crate btoi;
+
+use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
+use std::collections::HashMap;
+use std::io::BufRead;
+
+/// One of the eight ways to place a square: mirrored left to right first if
+/// `flipped`, then turned clockwise by `turns` quarter turns.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Orientation {
+    pub flipped: bool,
+    pub turns: u8,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 8] = [
+        Orientation::new(false, 0),
+        Orientation::new(false, 1),
+        Orientation::new(false, 2),
+        Orientation::new(false, 3),
+        Orientation::new(true, 0),
+        Orientation::new(true, 1),
+        Orientation::new(true, 2),
+        Orientation::new(true, 3),
+    ];
+
+    pub const fn new(flipped: bool, turns: u8) -> Self {
+        Orientation { flipped, turns }
+    }
+
+    /// Maps a position in a square of side `size`, placed in this orientation,
+    /// back to the same pixel's position in the original square.
+    pub fn source(self, size: usize, row: usize, column: usize) -> (usize, usize) {
+        let (mut row, mut column) = (row, column);
+        for _ in 0..self.turns % 4 {
+            let turned_row = size - 1 - column;
+            column = row;
+            row = turned_row;
+        }
+        if self.flipped {
+            column = size - 1 - column;
+        }
+        (row, column)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// A square of pixels with an id. Tiles are at most 32 pixels wide, so that a
+/// whole edge fits in a `u32`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tile {
+    id: u64,
+    size: usize,
+    pixels: Vec<bool>,
+}
+
+impl Tile {
+    pub fn new(id: u64, size: usize, pixels: Vec<bool>) -> Self {
+        assert!(size > 0 && size <= 32, "tile size must be 1 to 32");
+        assert_eq!(pixels.len(), size * size, "tile must be square");
+        Tile { id, size, pixels }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the pixel at `row` and `column` is set when the tile is placed in
+    /// the given orientation.
+    pub fn get(&self, orientation: Orientation, row: usize, column: usize) -> bool {
+        let (row, column) = orientation.source(self.size, row, column);
+        self.pixels[row * self.size + column]
+    }
+
+    /// The pixels along one side of the tile placed in the given orientation,
+    /// read left to right or top to bottom, with the first pixel in the lowest
+    /// bit. Two tiles fit next to each other when their facing edges are equal.
+    pub fn edge(&self, orientation: Orientation, side: Side) -> u32 {
+        let last = self.size - 1;
+        (0..self.size).fold(0, |edge, i| {
+            let (row, column) = match side {
+                Side::Top => (0, i),
+                Side::Right => (i, last),
+                Side::Bottom => (last, i),
+                Side::Left => (i, 0),
+            };
+            edge | (self.get(orientation, row, column) as u32) << i
+        })
+    }
+
+    /// The signatures of the top, right, bottom and left edges. A signature
+    /// stays the same however the tile is turned or flipped, so tiles can only
+    /// be neighbours if they have a signature in common.
+    pub fn signatures(&self) -> [u32; 4] {
+        let original = Orientation::new(false, 0);
+        let mut result = [0; 4];
+        for (i, side) in [Side::Top, Side::Right, Side::Bottom, Side::Left]
+            .iter()
+            .enumerate()
+        {
+            result[i] = signature(self.edge(original, *side), self.size);
+        }
+        result
+    }
+}
+
+// an edge and the same edge read backwards have the same signature
+fn signature(edge: u32, size: usize) -> u32 {
+    edge.min(edge.reverse_bits() >> (32 - size))
+}
+
+fn parse_header(text: &[u8], line: usize) -> Result<u64, ParseError> {
+    let id = text
+        .strip_prefix(b"Tile ")
+        .ok_or_else(|| ParseError::syntax(line, 1, &text[..text.len().min(5)], "\"Tile \""))?;
+    let id = id
+        .strip_suffix(b":")
+        .ok_or_else(|| ParseError::syntax(line, text.len() + 1, b"", "':'"))?;
+    btoi(id).map_err(|_| ParseError::syntax(line, 6, id, "tile id"))
+}
+
+pub fn parse<R>(reader: R) -> Result<Vec<Tile>, ParseError>
+where
+    R: BufRead,
+{
+    let mut lines = LineReader::new(reader);
+    let mut tiles = Vec::new();
+    // every tile has the width of the first tile's first row
+    let mut size = None;
+
+    while let Some((line, text)) = lines.next_line()? {
+        if text.is_empty() {
+            continue;
+        }
+        let id = parse_header(text, line)?;
+        let mut pixels = Vec::new();
+        let mut rows = 0;
+        while let Some((line, row)) = lines.next_line()? {
+            if row.is_empty() {
+                break;
+            }
+            let width = *size.get_or_insert(row.len().min(32));
+            if rows == width {
+                return Err(ParseError::syntax(line, 1, row, "end of tile"));
+            }
+            if row.len() > width {
+                return Err(ParseError::syntax(
+                    line,
+                    width + 1,
+                    &row[width..],
+                    "end of row",
+                ));
+            }
+            for column in 0..width {
+                match row.get(column) {
+                    Some(b'#') => pixels.push(true),
+                    Some(b'.') => pixels.push(false),
+                    found => {
+                        let found = found.map(std::slice::from_ref).unwrap_or(b"");
+                        return Err(ParseError::syntax(line, column + 1, found, "'#' or '.'"));
+                    }
+                }
+            }
+            rows += 1;
+        }
+        if size != Some(rows) {
+            return Err(ParseError::syntax(line + rows + 1, 1, b"", "tile row"));
+        }
+        tiles.push(Tile::new(id, rows, pixels));
+    }
+
+    Ok(tiles)
+}
+
+// the tiles that have an edge with each signature
+fn matches(tiles: &[Tile]) -> HashMap<u32, Vec<usize>> {
+    let mut result: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, tile) in tiles.iter().enumerate() {
+        for signature in tile.signatures().iter() {
+            result.entry(*signature).or_default().push(i);
+        }
+    }
+    result
+}
+
+fn is_outer(matches: &HashMap<u32, Vec<usize>>, signature: u32) -> bool {
+    matches.get(&signature).is_none_or(|tiles| tiles.len() == 1)
+}
+
+fn is_corner(tile: &Tile, matches: &HashMap<u32, Vec<usize>>) -> bool {
+    let signatures = tile.signatures();
+    signatures.iter().filter(|s| is_outer(matches, **s)).count() == 2
+}
+
+pub fn p1_solve(tiles: &[Tile]) -> Option<u64> {
+    let matches = matches(tiles);
+    let corners: Vec<u64> = tiles
+        .iter()
+        .filter(|tile| is_corner(tile, &matches))
+        .map(Tile::id)
+        .collect();
+    if corners.len() == 4 {
+        Some(corners.iter().product())
+    } else {
+        None
+    }
+}
+
+// places the tiles row by row, starting from a corner turned so that its
+// unmatched edges face up and left
+fn assemble(tiles: &[Tile]) -> Option<Vec<(usize, Orientation)>> {
+    let width = (1..=tiles.len()).find(|n| n * n >= tiles.len())?;
+    if width * width != tiles.len() {
+        return None;
+    }
+    let size = tiles[0].size();
+    let matches = matches(tiles);
+
+    let corner = tiles.iter().position(|tile| is_corner(tile, &matches))?;
+    let orientation = *Orientation::ALL.iter().find(|orientation| {
+        [Side::Top, Side::Left].iter().all(|side| {
+            let edge = tiles[corner].edge(**orientation, *side);
+            is_outer(&matches, signature(edge, size))
+        })
+    })?;
+
+    let mut placed = Vec::with_capacity(tiles.len());
+    let mut used = vec![false; tiles.len()];
+    placed.push((corner, orientation));
+    used[corner] = true;
+
+    for position in 1..tiles.len() {
+        let (neighbour, side, facing) = if position % width == 0 {
+            (placed[position - width], Side::Bottom, Side::Top)
+        } else {
+            (placed[position - 1], Side::Right, Side::Left)
+        };
+        let edge = tiles[neighbour.0].edge(neighbour.1, side);
+        let next = *matches
+            .get(&signature(edge, size))?
+            .iter()
+            .find(|i| !used[**i])?;
+        let orientation = *Orientation::ALL
+            .iter()
+            .find(|orientation| tiles[next].edge(**orientation, facing) == edge)?;
+        placed.push((next, orientation));
+        used[next] = true;
+    }
+
+    Some(placed)
+}
+
+const SEA_MONSTER: [&[u8]; 3] = [
+    b"                  # ",
+    b"#    ##    ##    ###",
+    b" #  #  #  #  #  #   ",
+];
+
+pub fn p2_solve(tiles: &[Tile]) -> Option<u64> {
+    let placed = assemble(tiles)?;
+    let width = (placed.len() as f64).sqrt() as usize;
+
+    // the image is the tiles without their borders
+    let inner = tiles[0].size().checked_sub(2)?;
+    let size = width * inner;
+    let mut image = vec![false; size * size];
+    for (i, (tile, orientation)) in placed.iter().enumerate() {
+        let top = i / width * inner;
+        let left = i % width * inner;
+        for row in 0..inner {
+            for column in 0..inner {
+                image[(top + row) * size + left + column] =
+                    tiles[*tile].get(*orientation, row + 1, column + 1);
+            }
+        }
+    }
+
+    let monster: Vec<(usize, usize)> = SEA_MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .filter(|(_, c)| **c == b'#')
+                .map(move |(column, _)| (row, column))
+        })
+        .collect();
+    let monster_height = SEA_MONSTER.len();
+    let monster_width = SEA_MONSTER[0].len();
+    if size < monster_width || size < monster_height {
+        return None;
+    }
+
+    // only one orientation of the image has sea monsters in it
+    for orientation in Orientation::ALL.iter() {
+        let get = |row, column| {
+            let (row, column) = orientation.source(size, row, column);
+            image[row * size + column]
+        };
+        let mut covered = vec![false; size * size];
+        let mut found = false;
+        for top in 0..=size - monster_height {
+            for left in 0..=size - monster_width {
+                if monster
+                    .iter()
+                    .all(|(row, column)| get(top + row, left + column))
+                {
+                    found = true;
+                    for (row, column) in monster.iter() {
+                        covered[(top + row) * size + left + column] = true;
+                    }
+                }
+            }
+        }
+        if found {
+            let rough = image.iter().filter(|pixel| **pixel).count();
+            let monsters = covered.iter().filter(|pixel| **pixel).count();
+            return Some((rough - monsters) as u64);
+        }
+    }
+
+    None
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Vec<Tile>;
+    type Output = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
@@ -0,0 +1,45 @@
+use d20::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn points_at_malformed_headers() {
+    assert_eq!(
+        error("Tile: 2311\n"),
+        "invalid input: line 1, column 1: expected \"Tile \", found \"Tile:\""
+    );
+    assert_eq!(
+        error("Tile 2311\n"),
+        "invalid input: line 1, column 10: expected ':', found end of line"
+    );
+    assert_eq!(
+        error("Tile x:\n"),
+        "invalid input: line 1, column 6: expected tile id, found \"x\""
+    );
+}
+
+#[test]
+fn tiles_are_squares_of_the_same_size() {
+    assert_eq!(
+        error("Tile 1:\n#.\n.#.\n"),
+        "invalid input: line 3, column 3: expected end of row, found \".\""
+    );
+    assert_eq!(
+        error("Tile 1:\n#.\n.\n"),
+        "invalid input: line 3, column 2: expected '#' or '.', found end of line"
+    );
+    assert_eq!(
+        error("Tile 1:\n#.\n.x\n"),
+        "invalid input: line 3, column 2: expected '#' or '.', found \"x\""
+    );
+    assert_eq!(
+        error("Tile 1:\n#.\n.#\n##\n"),
+        "invalid input: line 4, column 1: expected end of tile, found \"##\""
+    );
+    assert_eq!(
+        error("Tile 1:\n#.\n.#\n\nTile 2:\n#.\n\nTile 3:\n"),
+        "invalid input: line 7, column 1: expected tile row, found end of line"
+    );
+}
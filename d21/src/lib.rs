@@ -1,5 +1,6 @@
 extern crate rustc_hash;
 mod tokenizer;
+use common::{LineReader, ParseError, Solution};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::io::BufRead;
 use std::str::from_utf8;
@@ -7,22 +8,15 @@ use tokenizer::{TokenKind, Tokenizer};
 
 type ParserResult = Box<[(FxHashSet<Box<[u8]>>, FxHashSet<Box<[u8]>>)]>;
 
-pub fn parse<R>(mut reader: R) -> ParserResult
+pub fn parse<R>(reader: R) -> Result<ParserResult, ParseError>
 where
     R: BufRead,
 {
-    let mut input = Vec::new();
+    let input = LineReader::new(reader).read_to_end()?;
     let mut tokenizer = Tokenizer::new();
 
     let mut result = Vec::new();
-    let mut page: [u8; 4096] = [0; 4096];
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        tokenizer.tokenize(&mut (page[..page_len].iter()));
-        input.extend_from_slice(&page[..page_len]);
-    }
+    tokenizer.tokenize(&mut input.iter());
     tokenizer.flush();
 
     let mut token_iter = tokenizer.tokens.iter();
@@ -30,47 +24,86 @@ where
 
     while pos != input.len() {
         let mut ingredients = FxHashSet::default();
+        let mut opened = false;
         for token in &mut token_iter {
             match token.kind {
                 TokenKind::Literal => {
                     ingredients.insert(Box::from(&input[pos..pos + token.len]));
                     pos += token.len;
                 }
-                TokenKind::ParenthesesOpen => {
+                TokenKind::ParenthesesOpen if !ingredients.is_empty() => {
                     pos += token.len;
+                    opened = true;
                     break;
                 }
-                _ => {
+                TokenKind::Whitespace => {
                     pos += token.len;
                 }
+                TokenKind::EndOfLine if ingredients.is_empty() => {
+                    pos += token.len;
+                }
+                TokenKind::EndOfLine => {
+                    return Err(ParseError::at_offset(&input, pos, 0, "\"(contains\""));
+                }
+                _ => {
+                    return Err(ParseError::at_offset(&input, pos, token.len, "ingredient"));
+                }
+            }
+        }
+        if !opened {
+            if ingredients.is_empty() {
+                break;
             }
+            return Err(ParseError::at_offset(&input, pos, 0, "\"(contains\""));
         }
 
         let mut allergens = FxHashSet::default();
+        let mut contains = false;
+        let mut closed = false;
 
         for token in &mut token_iter {
+            let slice = &input[pos..pos + token.len];
             match token.kind {
-                TokenKind::Literal => {
-                    let slice = &input[pos..pos + token.len];
-                    if slice != b"contains" {
-                        allergens.insert(Box::from(slice));
-                    }
-                    pos += token.len;
+                TokenKind::Literal if contains => {
+                    allergens.insert(Box::from(slice));
                 }
-                TokenKind::ParenthesesClose => {
+                TokenKind::Literal if slice == b"contains" => {
+                    contains = true;
+                }
+                TokenKind::ParenthesesClose if !allergens.is_empty() => {
                     pos += token.len;
+                    closed = true;
                     break;
                 }
+                TokenKind::Whitespace => (),
+                TokenKind::Unknown if contains && slice == b"," => (),
+                _ if !contains => {
+                    return Err(ParseError::at_offset(
+                        &input,
+                        pos,
+                        token.len,
+                        "\"contains\"",
+                    ));
+                }
                 _ => {
-                    pos += token.len;
+                    return Err(ParseError::at_offset(
+                        &input,
+                        pos,
+                        token.len,
+                        "allergen or ')'",
+                    ));
                 }
             }
+            pos += token.len;
+        }
+        if !closed {
+            return Err(ParseError::at_offset(&input, pos, 0, "allergen or ')'"));
         }
 
         result.push((ingredients, allergens));
     }
 
-    result.into_boxed_slice()
+    Ok(result.into_boxed_slice())
 }
 
 fn remove_ingredient(
@@ -124,8 +157,7 @@ fn map_ingredients(parsed: &ParserResult) -> FxHashMap<&[u8], Box<[u8]>> {
 pub fn p1_solve(parsed: &ParserResult) -> Option<String> {
     let mapping = map_ingredients(parsed);
 
-    let allergens: FxHashSet<&Box<[u8]>> =
-        mapping.iter().map(|(_, ingredient)| ingredient).collect();
+    let allergens: FxHashSet<&Box<[u8]>> = mapping.values().collect();
 
     Some(
         parsed
@@ -147,7 +179,7 @@ pub fn p2_solve(parsed: &ParserResult) -> Option<String> {
         })
         .collect();
 
-    let mut ordered: Vec<&str> = str_mapping.iter().map(|(allergen, _)| *allergen).collect();
+    let mut ordered: Vec<&str> = str_mapping.keys().copied().collect();
     ordered.sort_unstable();
     let result = ordered
         .iter()
@@ -157,3 +189,22 @@ pub fn p2_solve(parsed: &ParserResult) -> Option<String> {
 
     Some(result)
 }
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = ParserResult;
+    type Output = String;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
@@ -43,7 +43,7 @@ impl Tokenizer {
                 b'(' => TokenKind::ParenthesesOpen,
                 b')' => TokenKind::ParenthesesClose,
                 _ => {
-                    if c >= &b'a' && c <= &b'z' {
+                    if (&b'a'..=&b'z').contains(&c) {
                         TokenKind::Literal
                     } else {
                         TokenKind::Unknown
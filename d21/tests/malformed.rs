@@ -0,0 +1,51 @@
+use d21::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+const FIRST: &str = "mxmxvkd kfcds (contains dairy, fish)\n";
+
+#[test]
+fn rejects_malformed_ingredients() {
+    assert_eq!(
+        error(&format!("{}sqjhc Fvjkl (contains soy)\n", FIRST)),
+        "invalid input: line 2, column 7: expected ingredient, found \"F\""
+    );
+    assert_eq!(
+        error(&format!("{}(contains soy)\n", FIRST)),
+        "invalid input: line 2, column 1: expected ingredient, found \"(\""
+    );
+    assert_eq!(
+        error(&format!("{}sqjhc fvjkl\n", FIRST)),
+        "invalid input: line 2, column 12: expected \"(contains\", found end of line"
+    );
+    assert_eq!(
+        error(&format!("{}sqjhc fvjkl", FIRST)),
+        "invalid input: line 2, column 12: expected \"(contains\", found end of line"
+    );
+}
+
+#[test]
+fn rejects_malformed_allergens() {
+    assert_eq!(
+        error(&format!("{}sqjhc (with soy)\n", FIRST)),
+        "invalid input: line 2, column 8: expected \"contains\", found \"with\""
+    );
+    assert_eq!(
+        error(&format!("{}sqjhc (contains soy; fish)\n", FIRST)),
+        "invalid input: line 2, column 20: expected allergen or ')', found \";\""
+    );
+    assert_eq!(
+        error(&format!("{}sqjhc (contains)\n", FIRST)),
+        "invalid input: line 2, column 16: expected allergen or ')', found \")\""
+    );
+    assert_eq!(
+        error(&format!("{}sqjhc (contains soy\n", FIRST)),
+        "invalid input: line 2, column 20: expected allergen or ')', found end of line"
+    );
+    assert_eq!(
+        error(&format!("{}sqjhc (contains soy", FIRST)),
+        "invalid input: line 2, column 20: expected allergen or ')', found end of line"
+    );
+}
@@ -1,81 +1,99 @@
 mod tokenizer;
 use btoi::btoi;
-use rustc_hash::{FxHashSet};
+use common::{LineReader, ParseError, Solution};
+use rustc_hash::FxHashSet;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::io::BufRead;
-use tokenizer::{LiteralKind, TokenKind, Tokenizer};
-
-pub fn parse<R>(mut reader: R) -> (VecDeque<u8>, VecDeque<u8>)
-where
-    R: BufRead,
-{
-    let mut input = Vec::new();
-    let mut tokenizer = Tokenizer::new();
-
-    let mut page: [u8; 4096] = [0; 4096];
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        tokenizer.tokenize(&mut (page[..page_len].iter()));
-        input.extend_from_slice(&page[..page_len]);
-    }
-    tokenizer.flush();
-
-    let mut token_iter = tokenizer.tokens.iter();
-    let mut pos = 0;
-
-    for token in &mut token_iter {
-        pos += token.len;
+use std::slice::Iter;
+use tokenizer::{LiteralKind, Token, TokenKind, Tokenizer};
+
+fn parse_header(
+    input: &[u8],
+    token_iter: &mut Iter<Token>,
+    pos: &mut usize,
+    header: &'static [u8],
+    expected: &'static str,
+) -> Result<(), ParseError> {
+    let start = *pos;
+    for token in token_iter {
+        *pos += token.len;
         if token.kind == TokenKind::EndOfLine {
             break;
         }
     }
+    // the end of line token takes in any blank lines after it too
+    let line = &input[start..*pos];
+    let line = line.split(|c| *c == b'\n').next().unwrap_or(line);
+    if line != header {
+        return Err(ParseError::at_offset(input, start, line.len(), expected));
+    }
+    Ok(())
+}
 
-    let mut p1 = VecDeque::new();
-
-    for token in &mut token_iter {
+// reads cards up to the next header, which is left for `parse_header`
+fn parse_deck(
+    input: &[u8],
+    token_iter: &mut Iter<Token>,
+    pos: &mut usize,
+) -> Result<VecDeque<u8>, ParseError> {
+    let mut deck = VecDeque::new();
+    while let Some(token) = token_iter.as_slice().first() {
         match token.kind {
             TokenKind::Literal(LiteralKind::Integer) => {
-                p1.push_back(btoi(&input[pos..pos + token.len]).expect("integer"));
-                pos += token.len;
-            }
-            TokenKind::Literal(LiteralKind::Char) => {
-                pos += token.len;
-                break;
-            }
-            _ => {
-                pos += token.len;
+                let card = btoi(&input[*pos..*pos + token.len])
+                    .map_err(|_| ParseError::at_offset(input, *pos, token.len, "card"))?;
+                deck.push_back(card);
             }
+            TokenKind::Literal(LiteralKind::Char) => break,
+            TokenKind::EndOfLine | TokenKind::Whitespace => (),
+            _ => return Err(ParseError::at_offset(input, *pos, token.len, "card")),
         }
+        *pos += token.len;
+        token_iter.next();
     }
+    Ok(deck)
+}
 
-    for token in &mut token_iter {
-        pos += token.len;
-        if token.kind == TokenKind::EndOfLine {
-            break;
-        }
-    }
+pub fn parse<R>(reader: R) -> Result<(VecDeque<u8>, VecDeque<u8>), ParseError>
+where
+    R: BufRead,
+{
+    let input = LineReader::new(reader).read_to_end()?;
+    let mut tokenizer = Tokenizer::new();
 
-    let mut p2 = VecDeque::new();
+    tokenizer.tokenize(&mut input.iter());
+    tokenizer.flush();
 
-    for token in &mut token_iter {
-        match token.kind {
-            TokenKind::Literal(LiteralKind::Integer) => {
-                p2.push_back(btoi(&input[pos..pos + token.len]).expect("integer"));
-                pos += token.len;
-            }
-            TokenKind::Literal(LiteralKind::Char) => {
-                break;
-            }
-            _ => {
-                pos += token.len;
-            }
-        }
+    let mut token_iter = tokenizer.tokens.iter();
+    let mut pos = 0;
+
+    parse_header(
+        &input,
+        &mut token_iter,
+        &mut pos,
+        b"Player 1:",
+        "\"Player 1:\"",
+    )?;
+    let p1 = parse_deck(&input, &mut token_iter, &mut pos)?;
+    parse_header(
+        &input,
+        &mut token_iter,
+        &mut pos,
+        b"Player 2:",
+        "\"Player 2:\"",
+    )?;
+    let p2 = parse_deck(&input, &mut token_iter, &mut pos)?;
+    if let Some(token) = token_iter.next() {
+        return Err(ParseError::at_offset(
+            &input,
+            pos,
+            token.len,
+            "end of input",
+        ));
     }
 
-    (p1, p2)
+    Ok((p1, p2))
 }
 
 pub fn p1_solve((p1, p2): &(VecDeque<u8>, VecDeque<u8>)) -> Option<usize> {
@@ -164,3 +182,22 @@ pub fn p2_solve((p1, p2): &(VecDeque<u8>, VecDeque<u8>)) -> Option<usize> {
             .sum(),
     )
 }
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = (VecDeque<u8>, VecDeque<u8>);
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
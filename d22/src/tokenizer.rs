@@ -47,9 +47,9 @@ impl Tokenizer {
                 b'\n' => TokenKind::EndOfLine,
                 b' ' => TokenKind::Whitespace,
                 _ => {
-                    if (c >= &b'a' && c <= &b'z') || (c >= &b'A' && c <= &b'Z') {
+                    if (&b'a'..=&b'z').contains(&c) || (&b'A'..=&b'Z').contains(&c) {
                         TokenKind::Literal(LiteralKind::Char)
-                    } else if c >= &b'0' && c <= &b'9' {
+                    } else if (&b'0'..=&b'9').contains(&c) {
                         TokenKind::Literal(LiteralKind::Integer)
                     } else {
                         TokenKind::Unknown
@@ -0,0 +1,40 @@
+use d22::{p1_solve, parse};
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn a_deck_can_be_empty() {
+    let decks = parse("Player 1:\n\nPlayer 2:\n1\n".as_bytes()).unwrap();
+    assert!(decks.0.is_empty());
+    assert_eq!(decks.1, vec![1]);
+    assert_eq!(p1_solve(&decks), Some(1));
+
+    let decks = parse("Player 1:\n3\n\nPlayer 2:\n\n\n".as_bytes()).unwrap();
+    assert_eq!((decks.0.len(), decks.1.len()), (1, 0));
+}
+
+#[test]
+fn headers_have_to_match() {
+    assert_eq!(
+        error("Player 3:\n\n\nPlayer 2:\n1\n"),
+        "invalid input: line 1, column 1: expected \"Player 1:\", found \"Player 3:\""
+    );
+    assert_eq!(
+        error("Player 1:\n1\n\nPlayer 2 \n1\n"),
+        "invalid input: line 4, column 1: expected \"Player 2:\", found \"Player 2 \""
+    );
+}
+
+#[test]
+fn cards_are_numbers_that_fit() {
+    assert_eq!(
+        error("Player 1:\n1\n\nPlayer 2:\n1\n5000\n"),
+        "invalid input: line 6, column 1: expected card, found \"5000\""
+    );
+    assert_eq!(
+        error("Player 1:\n1\n\nPlayer 2:\n1\nx\n"),
+        "invalid input: line 6, column 1: expected end of input, found \"x\""
+    );
+}
@@ -1,99 +1,135 @@
-use std::cmp::Ordering;
+use common::{LineReader, ParseError, Solution};
 use std::io::BufRead;
 
-pub fn parse<R>(mut reader: R) -> Box<[u8]>
+pub fn parse<R>(reader: R) -> Result<Box<[u8]>, ParseError>
 where
     R: BufRead,
 {
     let mut result = Vec::new();
-    let mut page: [u8; 10] = [0; 10];
-    if let Ok(page_len) = reader.read(&mut page) {
-        result.reserve(page_len);
-        for c in page[..page_len].iter() {
+    let mut lines = LineReader::new(reader);
+    if let Some((line, labels)) = lines.next_line()? {
+        result.reserve(labels.len());
+        for (i, c) in labels.iter().enumerate() {
             match c {
-                b'0' => result.push(0),
-                b'1' => result.push(1),
-                b'2' => result.push(2),
-                b'3' => result.push(3),
-                b'4' => result.push(4),
-                b'5' => result.push(5),
-                b'6' => result.push(6),
-                b'7' => result.push(7),
-                b'8' => result.push(8),
-                b'9' => result.push(9),
-                _ => (),
+                b'0'..=b'9' => result.push(c - b'0'),
+                _ => return Err(ParseError::syntax(line, i + 1, &[*c], "cup label")),
             }
         }
     }
-    result.into_boxed_slice()
+    Ok(result.into_boxed_slice())
 }
 
-fn round(parsed: &[u8], result: &mut [u8]) {
-    let current = &parsed[0];
-    let pick_up = &parsed[1..=3];
-    let remainder = &parsed[4..];
-
-    let target = current - 1;
-    let (mut result_i, mut result_v) = (0, 0);
-
-    for (i, v) in remainder.iter().enumerate() {
-        match v.cmp(&target) {
-            Ordering::Equal => {
-                result_i = i;
-                result_v = *v;
-                break;
-            },
-            Ordering::Less => {
-                if v > &result_v || (result_v > target) {
-                    result_i = i;
-                    result_v = *v;
-                }
-            },
-            Ordering::Greater => {
-                if result_v == 0 || (result_v > target && v > &result_v) {
-                    result_i = i;
-                    result_v = *v;
-                }
+// the cups in their circle, as the label of the cup clockwise of each label
+struct Cups {
+    next: Vec<u32>,
+    current: u32,
+}
+
+impl Cups {
+    // the given labels first, then the labels after the highest of them up to
+    // `total`; the labels must run from 1 to however many of them there are
+    fn new(labels: &[u8], total: u32) -> Option<Self> {
+        let count = labels.len() as u32;
+        if count < 4 || total < count {
+            return None;
+        }
+        let mut seen = vec![false; labels.len() + 1];
+        for label in labels {
+            match seen.get_mut(*label as usize) {
+                Some(seen @ false) if *label > 0 => *seen = true,
+                _ => return None,
             }
         }
+
+        let mut next = vec![0; total as usize + 1];
+        let mut order = labels
+            .iter()
+            .map(|label| *label as u32)
+            .chain(count + 1..=total);
+        let first = order.next()?;
+        let last = order.fold(first, |previous, label| {
+            next[previous as usize] = label;
+            label
+        });
+        next[last as usize] = first;
+
+        Some(Cups {
+            next,
+            current: first,
+        })
     }
 
-    let mut pos = 1;
-    for n in &remainder[..result_i] {
-        result[pos] = *n;
-        pos += 1;
+    fn after(&self, label: u32) -> u32 {
+        self.next[label as usize]
     }
-    result[pos] = result_v;
-    pos += 1;
-    for n in pick_up {
-        result[pos] = *n;
-        pos += 1;
+
+    fn play(&mut self, moves: usize) {
+        let highest = self.next.len() as u32 - 1;
+        let next = &mut self.next;
+        let mut current = self.current;
+        for _ in 0..moves {
+            let first = next[current as usize];
+            let second = next[first as usize];
+            let third = next[second as usize];
+
+            let mut destination = current;
+            loop {
+                destination = if destination == 1 {
+                    highest
+                } else {
+                    destination - 1
+                };
+                if destination != first && destination != second && destination != third {
+                    break;
+                }
+            }
+
+            next[current as usize] = next[third as usize];
+            next[third as usize] = next[destination as usize];
+            next[destination as usize] = first;
+            current = next[current as usize];
+        }
+        self.current = current;
     }
-    for n in &remainder[result_i + 1..] {
-        result[pos] = *n;
-        pos += 1;
+}
+
+pub fn p1_solve(parsed: &[u8]) -> Option<String> {
+    let mut cups = Cups::new(parsed, parsed.len() as u32)?;
+    cups.play(100);
+
+    let mut result = String::with_capacity(parsed.len() - 1);
+    let mut label = cups.after(1);
+    while label != 1 {
+        result.push_str(&label.to_string());
+        label = cups.after(label);
     }
+    Some(result)
+}
+
+pub fn p2_solve(parsed: &[u8]) -> Option<String> {
+    let mut cups = Cups::new(parsed, 1_000_000)?;
+    cups.play(10_000_000);
 
-    result.rotate_left(1);
+    let first = cups.after(1);
+    let second = cups.after(first);
+    Some((first as u64 * second as u64).to_string())
 }
 
-pub fn p1_solve(parsed: &Box<[u8]>) -> Option<String> {
+pub struct Day23;
 
-    let mut parsed = parsed.clone();
+impl Solution for Day23 {
+    type Input = Box<[u8]>;
+    type Output = String;
 
-    for _ in 0..100 {
-        let mut result = parsed.clone();
-        round(&parsed, &mut result);
-        parsed = result.clone();
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
     }
 
-    while parsed[0] != 1 {
-        parsed.rotate_left(1);
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
     }
 
-    Some(parsed[1..].iter().map(|i| i.to_string()).collect::<String>())
-}
-
-pub fn p2_solve(parsed: &Box<[u8]>) -> Option<String> {
-    None
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
 }
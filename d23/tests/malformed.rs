@@ -0,0 +1,21 @@
+use d23::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn cup_labels_are_digits() {
+    assert_eq!(
+        error("389a25467\n"),
+        "invalid input: line 1, column 4: expected cup label, found \"a\""
+    );
+    assert_eq!(
+        error("389 125467\n"),
+        "invalid input: line 1, column 4: expected cup label, found \" \""
+    );
+    assert_eq!(
+        &*parse("389125467\r\n".as_bytes()).unwrap(),
+        &[3, 8, 9, 1, 2, 5, 4, 6, 7]
+    );
+}
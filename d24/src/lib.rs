@@ -1,4 +1,5 @@
 extern crate rustc_hash;
+use common::{LineReader, ParseError, Solution};
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::io::BufRead;
 
@@ -18,54 +19,55 @@ pub enum Direction {
     NorthWest,
 }
 
-pub fn parse<R>(mut reader: R) -> Vec<Vec<Direction>>
-where
-    R: BufRead,
-{
-    let mut result = Vec::new();
-    let mut page: [u8; 4096] = [0; 4096];
-    let mut directions = Vec::new();
+fn parse_line(text: &[u8], line: usize) -> Result<Vec<Direction>, ParseError> {
+    let mut directions = Vec::with_capacity(text.len());
     let mut state = ParserState::None;
-    while let Ok(page_len) = reader.read(&mut page) {
-        if page_len == 0 {
-            break;
-        }
-        for c in page[..page_len].iter() {
-            match *c {
-                b'\n' => {
-                    result.push(directions.clone());
-                    directions.clear();
-                }
-                b'n' => {
-                    state = ParserState::North;
-                }
-                b's' => {
-                    state = ParserState::South;
-                }
-                b'w' => {
-                    match state {
-                        ParserState::None => directions.push(Direction::West),
-                        ParserState::South => directions.push(Direction::SouthWest),
-                        ParserState::North => directions.push(Direction::NorthWest),
-                    }
-                    state = ParserState::None;
+    for (i, c) in text.iter().enumerate() {
+        match (*c, &state) {
+            (b'n', ParserState::None) => {
+                state = ParserState::North;
+            }
+            (b's', ParserState::None) => {
+                state = ParserState::South;
+            }
+            (b'w', _) => {
+                match state {
+                    ParserState::None => directions.push(Direction::West),
+                    ParserState::South => directions.push(Direction::SouthWest),
+                    ParserState::North => directions.push(Direction::NorthWest),
                 }
-                b'e' => {
-                    match state {
-                        ParserState::None => directions.push(Direction::East),
-                        ParserState::South => directions.push(Direction::SouthEast),
-                        ParserState::North => directions.push(Direction::NorthEast),
-                    }
-                    state = ParserState::None;
+                state = ParserState::None;
+            }
+            (b'e', _) => {
+                match state {
+                    ParserState::None => directions.push(Direction::East),
+                    ParserState::South => directions.push(Direction::SouthEast),
+                    ParserState::North => directions.push(Direction::NorthEast),
                 }
-                _ => (),
+                state = ParserState::None;
             }
+            (_, ParserState::None) => {
+                return Err(ParseError::syntax(line, i + 1, &[*c], "direction"));
+            }
+            _ => return Err(ParseError::syntax(line, i + 1, &[*c], "'e' or 'w'")),
         }
     }
-    if !directions.is_empty() {
-        result.push(directions);
+    match state {
+        ParserState::None => Ok(directions),
+        _ => Err(ParseError::syntax(line, text.len() + 1, b"", "'e' or 'w'")),
+    }
+}
+
+pub fn parse<R>(reader: R) -> Result<Vec<Vec<Direction>>, ParseError>
+where
+    R: BufRead,
+{
+    let mut result = Vec::new();
+    let mut lines = LineReader::new(reader);
+    while let Some((line, text)) = lines.next_line()? {
+        result.push(parse_line(text, line)?);
     }
-    result
+    Ok(result)
 }
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -164,7 +166,7 @@ pub fn p1_solve(directions: &[Vec<Direction>]) -> Option<usize> {
     for tile in directions {
         coordinate = Coordinate::new();
         for direction in tile {
-            coordinate.move_towards(&direction);
+            coordinate.move_towards(direction);
         }
         if flipped.contains(&coordinate) {
             flipped.remove(&coordinate);
@@ -181,7 +183,7 @@ pub fn p2_solve(directions: &[Vec<Direction>]) -> Option<usize> {
     for tile in directions {
         coordinate = Coordinate::new();
         for direction in tile {
-            coordinate.move_towards(&direction);
+            coordinate.move_towards(direction);
         }
         if black_tiles.contains(&coordinate) {
             black_tiles.remove(&coordinate);
@@ -214,3 +216,22 @@ pub fn p2_solve(directions: &[Vec<Direction>]) -> Option<usize> {
 
     Some(black_tiles.len())
 }
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Vec<Direction>>;
+    type Output = usize;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
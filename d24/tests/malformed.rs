@@ -0,0 +1,25 @@
+use d24::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn points_at_the_direction() {
+    assert_eq!(
+        error("esew\nnwwswee\nnwx\n"),
+        "invalid input: line 3, column 3: expected direction, found \"x\""
+    );
+    assert_eq!(
+        error("esew\nnex\n"),
+        "invalid input: line 2, column 3: expected direction, found \"x\""
+    );
+    assert_eq!(
+        error("sn\n"),
+        "invalid input: line 1, column 2: expected 'e' or 'w', found \"n\""
+    );
+    assert_eq!(
+        error("esew\nnwws\n"),
+        "invalid input: line 2, column 5: expected 'e' or 'w', found end of line"
+    );
+}
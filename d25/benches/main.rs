@@ -0,0 +1,10 @@
+use common::bench::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use d25::Day25;
+
+fn main_benchmark(c: &mut Criterion) {
+    bench::<Day25>(c, env!("CARGO_MANIFEST_DIR"));
+}
+
+criterion_group!(benches, main_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,99 @@
+extern crate btoi;
+
+use btoi::btoi;
+use common::{LineReader, ParseError, Solution};
+use std::collections::HashMap;
+use std::io::BufRead;
+
+const MODULUS: u64 = 20201227;
+const SUBJECT: u64 = 7;
+
+pub fn parse<R>(reader: R) -> Result<(u64, u64), ParseError>
+where
+    R: BufRead,
+{
+    let mut lines = LineReader::new(reader);
+    let mut keys = [0; 2];
+
+    // the card's public key, then the door's
+    for (i, key) in keys.iter_mut().enumerate() {
+        let (line, text) = lines.next_line()?.unwrap_or((i + 1, b""));
+        *key = btoi::<u64>(text)
+            .ok()
+            .filter(|key| *key < MODULUS)
+            .ok_or_else(|| ParseError::syntax(line, 1, text, "public key"))?;
+    }
+
+    while let Some((line, rest)) = lines.next_line()? {
+        if !rest.is_empty() {
+            return Err(ParseError::syntax(line, 1, rest, "end of input"));
+        }
+    }
+
+    Ok((keys[0], keys[1]))
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64) -> u64 {
+    let mut result = 1;
+    base %= MODULUS;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % MODULUS;
+        }
+        base = base * base % MODULUS;
+        exponent >>= 1;
+    }
+    result
+}
+
+// baby-step giant-step: with m = ceil(sqrt(MODULUS)), every loop size is
+// i * m + j for some i, j < m, so remember SUBJECT^j for every j and step
+// through public_key * SUBJECT^(-m * i) until one of them turns up
+fn loop_size(public_key: u64) -> Option<u64> {
+    let m = (MODULUS as f64).sqrt().ceil() as u64;
+
+    let mut baby_steps = HashMap::with_capacity(m as usize);
+    let mut value = 1;
+    for j in 0..m {
+        baby_steps.entry(value).or_insert(j);
+        value = value * SUBJECT % MODULUS;
+    }
+
+    // SUBJECT^(-m), by Fermat's little theorem as the modulus is prime
+    let giant_step = pow_mod(SUBJECT, MODULUS - 1 - m);
+    let mut value = public_key;
+    for i in 0..m {
+        if let Some(j) = baby_steps.get(&value) {
+            return Some(i * m + j);
+        }
+        value = value * giant_step % MODULUS;
+    }
+    None
+}
+
+pub fn p1_solve((card_key, door_key): &(u64, u64)) -> Option<u64> {
+    loop_size(*card_key).map(|card_loop_size| pow_mod(*door_key, card_loop_size))
+}
+
+pub fn p2_solve(_parsed: &(u64, u64)) -> Option<u64> {
+    None
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = (u64, u64);
+    type Output = u64;
+
+    fn parse<R: BufRead>(reader: R) -> Result<Self::Input, ParseError> {
+        parse(reader)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Self::Output> {
+        p1_solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Self::Output> {
+        p2_solve(input)
+    }
+}
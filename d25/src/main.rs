@@ -0,0 +1,32 @@
+extern crate d25;
+use d25::{p1_solve, p2_solve, parse};
+use std::{env, io, process};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    let part = args
+        .get(1)
+        .expect("Missing argument 'part'")
+        .trim()
+        .parse()
+        .expect("Part must be a number");
+
+    let parsed = match parse(io::stdin().lock()) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    let result = match part {
+        1 => p1_solve(&parsed),
+        2 => p2_solve(&parsed),
+        _ => None,
+    };
+
+    if let Some(result) = result {
+        println!("{}", result);
+    }
+}
@@ -0,0 +1,25 @@
+use d25::parse;
+
+fn error(input: &str) -> String {
+    parse(input.as_bytes()).err().unwrap().to_string()
+}
+
+#[test]
+fn takes_two_public_keys_and_nothing_else() {
+    assert_eq!(
+        error("5764801\nkey\n"),
+        "invalid input: line 2, column 1: expected public key, found \"key\""
+    );
+    assert_eq!(
+        error("5764801\n20201227\n"),
+        "invalid input: line 2, column 1: expected public key, found \"20201227\""
+    );
+    assert_eq!(
+        error("5764801\n"),
+        "invalid input: line 2, column 1: expected public key, found end of line"
+    );
+    assert_eq!(
+        error("5764801\n17807724\n\n8\n"),
+        "invalid input: line 4, column 1: expected end of input, found \"8\""
+    );
+}